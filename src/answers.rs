@@ -0,0 +1,166 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::solution::Answers;
+use crate::{oops, oops::Oops};
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+// If set, expected answers are looked up in this directory instead of `answers/` under the current
+// directory.
+pub const ANSWER_DIR_VAR: &str = "AOC_ANSWER_DIR";
+
+// Answer files hold exactly what the day binaries print: part 1 on the first line, followed by
+// part 2, which may span multiple lines (e.g. day 10's rendered letters).
+#[must_use]
+pub fn render(answers: &Answers) -> String {
+    format!("{}\n{}\n", answers.part1, answers.part2)
+}
+
+pub fn parse(s: &str) -> Result<Answers, Oops> {
+    let (part1, part2) = s
+        .split_once('\n')
+        .ok_or_else(|| oops!("answers must have at least two lines"))?;
+    let part2 = part2
+        .strip_suffix('\n')
+        .ok_or_else(|| oops!("answers must end with a newline"))?;
+    Ok(Answers {
+        part1: part1.to_string(),
+        part2: part2.to_string(),
+    })
+}
+
+#[must_use]
+pub fn answer_dir() -> PathBuf {
+    env::var_os(ANSWER_DIR_VAR).map_or_else(|| PathBuf::from("answers"), PathBuf::from)
+}
+
+#[must_use]
+pub fn answer_path(day: u32) -> PathBuf {
+    answer_dir().join(format!("day{day:02}.txt"))
+}
+
+// Returns `None` if there are no recorded answers for `day`.
+pub fn load(day: u32) -> Result<Option<Answers>, Oops> {
+    let path = answer_path(day);
+    match fs::read_to_string(&path) {
+        Ok(s) => {
+            Ok(Some(parse(&s).map_err(|e| {
+                oops!("bad answers in {}: {e}", path.display())
+            })?))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(oops!("cannot read answers {}: {e}", path.display())),
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    pub part: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(Vec<Mismatch>),
+    Missing,
+}
+
+#[must_use]
+pub fn check(expected: Option<&Answers>, actual: &Answers) -> Verdict {
+    let Some(expected) = expected else {
+        return Verdict::Missing;
+    };
+    let mismatches: Vec<_> = [
+        (1, &expected.part1, &actual.part1),
+        (2, &expected.part2, &actual.part2),
+    ]
+    .into_iter()
+    .filter(|(_, expected, actual)| expected != actual)
+    .map(|(part, expected, actual)| Mismatch {
+        part,
+        expected: expected.clone(),
+        actual: actual.clone(),
+    })
+    .collect();
+    if mismatches.is_empty() {
+        Verdict::Pass
+    } else {
+        Verdict::Fail(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use std::path::Path;
+
+    fn answers(part1: &str, part2: &str) -> Answers {
+        Answers {
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let a = answers("12460", "#..#\n.##.\n");
+        let b = parse(&render(&a)).unwrap();
+        assert_eq!(a.part1, b.part1);
+        assert_eq!(a.part2, b.part2);
+    }
+
+    #[test]
+    fn parse_rejects_truncated() {
+        assert!(parse("").is_err());
+        assert!(parse("1").is_err());
+        assert!(parse("1\n2").is_err());
+    }
+
+    #[test]
+    fn check_verdicts() {
+        let expected = answers("1", "2");
+        assert_eq!(Verdict::Missing, check(None, &answers("1", "2")));
+        assert_eq!(Verdict::Pass, check(Some(&expected), &answers("1", "2")));
+        assert_eq!(
+            Verdict::Fail(vec![Mismatch {
+                part: 2,
+                expected: "2".to_string(),
+                actual: "3".to_string(),
+            }]),
+            check(Some(&expected), &answers("1", "3"))
+        );
+    }
+
+    // The only test that sets the variable, so it cannot race with the others.
+    #[test]
+    fn answer_dir_override() {
+        let dir = TempDir::new("answers_override");
+        fs::write(dir.join("day03.txt"), "1\n2\n").unwrap();
+        fs::write(dir.join("day04.txt"), "1\n").unwrap();
+        env::set_var(ANSWER_DIR_VAR, dir.path());
+        let (found, bad, missing) = (load(3), load(4), load(5));
+        env::remove_var(ANSWER_DIR_VAR);
+        let found = found.unwrap().unwrap();
+        assert_eq!(("1", "2"), (found.part1.as_str(), found.part2.as_str()));
+        assert!(bad.is_err());
+        assert!(missing.unwrap().is_none());
+        assert_eq!(Path::new("answers/day03.txt"), answer_path(3));
+    }
+}
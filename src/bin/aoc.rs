@@ -0,0 +1,386 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use aoc_2022::answers::{self, Verdict};
+use aoc_2022::days;
+use aoc_2022::input::InputSource;
+use aoc_2022::json::Json;
+use aoc_2022::parallel;
+use aoc_2022::solution::{Run, Stats};
+use aoc_2022::{oops, oops::Oops};
+use std::env;
+use std::time::Duration;
+
+const USAGE: &str = concat!(
+    "usage: aoc [--time] [--bench <runs>] [--format text|json] [--jobs <n>]\n",
+    "           [--solver-threads <n>] (<day> [<input>|-] | all)\n",
+    "       aoc verify [--jobs <n>] [--solver-threads <n>] [<day> | all]"
+);
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Command {
+    Solve,
+    Verify,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+struct Options {
+    command: Command,
+    days: Vec<u32>,
+    input: Option<String>,
+    time: bool,
+    bench: Option<usize>,
+    format: Format,
+    jobs: Option<usize>,
+    solver_threads: usize,
+}
+
+impl Options {
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, Oops> {
+        let mut positional = vec![];
+        let mut time = false;
+        let mut bench = None;
+        let mut format = Format::Text;
+        let mut jobs = None;
+        let mut solver_threads = 1;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--time" => time = true,
+                "--bench" => {
+                    let runs = args
+                        .next()
+                        .and_then(|runs| runs.parse().ok())
+                        .filter(|runs| *runs > 0)
+                        .ok_or_else(|| oops!("--bench needs a positive number of runs"))?;
+                    bench = Some(runs);
+                }
+                "--format" => {
+                    format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        _ => Err(oops!("--format must be text or json"))?,
+                    };
+                }
+                "--jobs" => {
+                    let n = args
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| oops!("--jobs needs a positive number of threads"))?;
+                    jobs = Some(n);
+                }
+                "--solver-threads" => {
+                    solver_threads = args
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| oops!("--solver-threads needs a positive number"))?;
+                }
+                _ => positional.push(arg),
+            }
+        }
+        let mut positional = positional.into_iter().peekable();
+        let command = if positional.next_if(|arg| arg == "verify").is_some() {
+            Command::Verify
+        } else {
+            Command::Solve
+        };
+        let days = match (command, positional.next().as_deref()) {
+            (_, Some("all")) | (Command::Verify, None) => days::ALL.collect(),
+            (_, Some(day)) => vec![day.parse().map_err(|_| oops!("{USAGE}"))?],
+            (Command::Solve, None) => Err(oops!("{USAGE}"))?,
+        };
+        let input = positional.next();
+        if positional.next().is_some()
+            || (input.is_some() && (days.len() > 1 || command == Command::Verify))
+        {
+            return Err(oops!("{USAGE}"));
+        }
+        // Verification only reports pass or fail, so timing and output options make no sense.
+        if command == Command::Verify && (time || bench.is_some() || format != Format::Text) {
+            return Err(oops!("{USAGE}"));
+        }
+        Ok(Options {
+            command,
+            days,
+            input,
+            time,
+            bench,
+            format,
+            jobs,
+            solver_threads,
+        })
+    }
+
+    fn source(&self, day: u32) -> InputSource {
+        InputSource::from_arg(day, self.input.as_deref())
+    }
+
+    fn multiple_days(&self) -> bool {
+        self.days.len() > 1
+    }
+
+    // Timed runs and benchmarks default to running one day at a time, since days competing for
+    // cores would skew each other's timings.
+    fn jobs(&self) -> usize {
+        match self.jobs {
+            Some(jobs) => jobs,
+            None if self.time || self.bench.is_some() => 1,
+            None => parallel::default_jobs(),
+        }
+    }
+
+    // Runs every requested day on the worker pool, handing results back in day order.
+    fn for_each_day<R, F, E>(&self, f: F, mut emit: E)
+    where
+        R: Send,
+        F: Fn(u32) -> R + Sync,
+        E: FnMut(u32, R),
+    {
+        parallel::for_each_ordered(
+            &self.days,
+            self.jobs(),
+            |day| f(*day),
+            |day, r| emit(*day, r),
+        );
+    }
+
+    fn run_day(&self, day: u32, runs: usize) -> Result<Run, Oops> {
+        let solver = days::solver(day).ok_or_else(|| oops!("no solution for day {day}"))?;
+        solver(&self.source(day).load()?, runs, self.solver_threads)
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.0)
+}
+
+// Each of these returns the number of days that failed.
+
+fn solve(options: &Options) -> usize {
+    let mut failures = 0;
+    options.for_each_day(
+        |day| options.run_day(day, 1),
+        |day, result| {
+            if options.multiple_days() {
+                println!("Day {day:02}");
+            }
+            match result {
+                Ok(run) => {
+                    println!("{}", run.answers.part1);
+                    println!("{}", run.answers.part2);
+                    if options.time {
+                        let timings = &run.timings[0];
+                        println!(
+                            "time: parse {}, part1 {}, part2 {}",
+                            format_duration(timings.parse),
+                            format_duration(timings.part1),
+                            format_duration(timings.part2)
+                        );
+                    }
+                }
+                Err(e) => {
+                    println!("error: {e}");
+                    failures += 1;
+                }
+            }
+        },
+    );
+    failures
+}
+
+fn bench(options: &Options, runs: usize) -> usize {
+    let mut failures = 0;
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "day", "phase", "min", "median", "max"
+    );
+    options.for_each_day(
+        |day| options.run_day(day, runs),
+        |day, result| {
+            let run = match result {
+                Ok(run) => run,
+                Err(e) => {
+                    eprintln!("day {day:02}: error: {e}");
+                    failures += 1;
+                    return;
+                }
+            };
+            let phases = [
+                (
+                    "parse",
+                    run.timings.iter().map(|t| t.parse).collect::<Vec<_>>(),
+                ),
+                ("part1", run.timings.iter().map(|t| t.part1).collect()),
+                ("part2", run.timings.iter().map(|t| t.part2).collect()),
+            ];
+            for (phase, samples) in phases {
+                let Some(stats) = Stats::from_samples(samples) else {
+                    continue;
+                };
+                println!(
+                    "{day:>3}  {phase:<5}  {:>12}  {:>12}  {:>12}",
+                    format_duration(stats.min),
+                    format_duration(stats.median),
+                    format_duration(stats.max)
+                );
+            }
+        },
+    );
+    failures
+}
+
+fn duration_ms(d: Duration) -> Json {
+    Json::from(d.as_secs_f64() * 1000.0)
+}
+
+// With a single sample, only `time_ms` is reported; benchmarks add the spread as well.
+fn timing_fields(samples: Vec<Duration>) -> Vec<(&'static str, Json)> {
+    let runs = samples.len();
+    let Some(stats) = Stats::from_samples(samples) else {
+        return vec![];
+    };
+    let mut fields = vec![("time_ms", duration_ms(stats.median))];
+    if runs > 1 {
+        fields.push(("min_ms", duration_ms(stats.min)));
+        fields.push(("max_ms", duration_ms(stats.max)));
+    }
+    fields
+}
+
+// Multi-line answers (e.g. day 10's letters) are reported as art rather than as the answer.
+fn answer_fields(answer: &str) -> [(&'static str, Json); 2] {
+    if answer.contains('\n') {
+        [("answer", Json::Null), ("art", Json::from(answer))]
+    } else if let Ok(n) = answer.parse::<i64>() {
+        [("answer", Json::from(n)), ("art", Json::Null)]
+    } else {
+        [("answer", Json::from(answer)), ("art", Json::Null)]
+    }
+}
+
+fn day_json(day: u32, runs: usize, result: &Result<Run, Oops>) -> Json {
+    let (parse, parts, error) = match result {
+        Ok(run) => {
+            let parse = Json::object(timing_fields(run.timings.iter().map(|t| t.parse).collect()));
+            let parts = [
+                (
+                    1,
+                    &run.answers.part1,
+                    run.timings.iter().map(|t| t.part1).collect(),
+                ),
+                (
+                    2,
+                    &run.answers.part2,
+                    run.timings.iter().map(|t| t.part2).collect(),
+                ),
+            ]
+            .into_iter()
+            .map(|(part, answer, samples): (u32, _, Vec<_>)| {
+                let mut fields = vec![("part", Json::from(part))];
+                fields.extend(answer_fields(answer));
+                fields.extend(timing_fields(samples));
+                Json::object(fields)
+            })
+            .collect();
+            (parse, parts, Json::Null)
+        }
+        Err(e) => (Json::Null, vec![], Json::from(e.to_string())),
+    };
+    Json::object([
+        ("day", Json::from(day)),
+        ("runs", Json::from(runs)),
+        ("parse", parse),
+        ("parts", Json::Array(parts)),
+        ("error", error),
+    ])
+}
+
+// Emits one JSON object per line, one line per day.
+fn report_json(options: &Options, runs: usize) -> usize {
+    let mut failures = 0;
+    options.for_each_day(
+        |day| options.run_day(day, runs),
+        |day, result| {
+            if result.is_err() {
+                failures += 1;
+            }
+            println!("{}", day_json(day, runs, &result));
+        },
+    );
+    failures
+}
+
+fn verify(options: &Options) -> usize {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    options.for_each_day(
+        |day| {
+            answers::load(day).and_then(|expected| {
+                let run = options.run_day(day, 1)?;
+                Ok(answers::check(expected.as_ref(), &run.answers))
+            })
+        },
+        |day, verdict| match verdict {
+            Ok(Verdict::Pass) => {
+                println!("Day {day:02}: pass");
+                passed += 1;
+            }
+            Ok(Verdict::Fail(mismatches)) => {
+                println!("Day {day:02}: fail");
+                for m in mismatches {
+                    println!(
+                        "  part {}: expected {:?}, got {:?}",
+                        m.part, m.expected, m.actual
+                    );
+                }
+                failed += 1;
+            }
+            Ok(Verdict::Missing) => {
+                println!("Day {day:02}: missing");
+                missing += 1;
+            }
+            Err(e) => {
+                println!("Day {day:02}: fail");
+                println!("  error: {e}");
+                failed += 1;
+            }
+        },
+    );
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    failed
+}
+
+fn main() -> Result<(), Oops> {
+    let options = Options::parse(env::args().skip(1))?;
+
+    let failures = match (options.command, options.bench) {
+        (Command::Verify, _) => verify(&options),
+        (Command::Solve, runs) if options.format == Format::Json => {
+            report_json(&options, runs.unwrap_or(1))
+        }
+        (Command::Solve, Some(runs)) => bench(&options, runs),
+        (Command::Solve, None) => solve(&options),
+    };
+
+    match failures {
+        0 => Ok(()),
+        1 if !options.multiple_days() => Err(oops!("day {:02} failed", options.days[0])),
+        _ => Err(oops!("{failures} day(s) failed")),
+    }
+}
@@ -12,69 +12,10 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
-use aoc_2022::{oops, oops::Oops};
-use std::io::{self, Read};
-use std::str::FromStr;
-
-struct Puzzle {
-    values: Vec<usize>,
-}
-
-impl FromStr for Puzzle {
-    type Err = Oops;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Puzzle {
-            values: s.lines().map(str::parse).collect::<Result<Vec<_>, _>>()?,
-        })
-    }
-}
-
-fn parse(input: &str) -> Result<Puzzle, Oops> {
-    input.parse()
-}
-
-fn part1(puzzle: &Puzzle) -> usize {
-    puzzle.values.iter().sum()
-}
-
-fn part2(puzzle: &Puzzle) -> usize {
-    *puzzle.values.iter().max().unwrap()
-}
+use aoc_2022::days::day00::Day00;
+use aoc_2022::oops::Oops;
+use aoc_2022::solution;
 
 fn main() -> Result<(), Oops> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-    let input = input;
-
-    let puzzle = parse(&input)?;
-
-    println!("{}", part1(&puzzle));
-    println!("{}", part2(&puzzle));
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const SAMPLE: &str = concat!(
-        // First line!
-        "123456789\n",
-        // Second line!
-        "987654321\n",
-        // Third line!
-        "2468013579\n"
-    );
-
-    #[test]
-    fn example1() {
-        assert_eq!(3579124689, part1(&parse(SAMPLE).unwrap()));
-    }
-
-    #[test]
-    fn example2() {
-        assert_eq!(2468013579, part2(&parse(SAMPLE).unwrap()));
-    }
+    solution::run::<Day00>(0)
 }
@@ -12,42 +12,10 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
-use aoc_2022::itertools::IterTools;
-use std::{collections::BTreeSet, io};
+use aoc_2022::days::day01::Day01;
+use aoc_2022::oops::Oops;
+use aoc_2022::solution;
 
-// Would be nice if rustfmt sorted this...
-#[derive(Ord, Eq, PartialOrd, PartialEq)]
-struct Elf {
-    total_rations: u32,
-}
-
-impl Elf {
-    fn new(total_rations: u32) -> Elf {
-        Elf { total_rations }
-    }
-}
-
-fn main() {
-    let elves: BTreeSet<Elf> = io::stdin()
-        .lines()
-        .segment(|line| line.as_ref().unwrap().is_empty())
-        .map(|group| {
-            Elf::new(
-                group
-                    .iter()
-                    .map(|line| line.as_ref().unwrap().parse::<u32>().unwrap())
-                    .sum(),
-            )
-        })
-        .collect();
-    println!("{}", elves.iter().last().unwrap().total_rations);
-    println!(
-        "{}",
-        elves
-            .iter()
-            .rev()
-            .take(3)
-            .map(|elf| elf.total_rations)
-            .sum::<u32>()
-    );
+fn main() -> Result<(), Oops> {
+    solution::run::<Day01>(1)
 }
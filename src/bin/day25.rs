@@ -12,170 +12,10 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
-use aoc_2022::{oops, oops::Oops};
-use std::io::{self, Read};
-use std::str::FromStr;
-
-struct Puzzle {
-    values: Vec<Snafu>,
-}
-
-enum SnafuDigit {
-    DoubleMinus,
-    Minus,
-    Zero,
-    One,
-    Two,
-}
-
-impl SnafuDigit {
-    fn to_i64(&self) -> i64 {
-        match self {
-            SnafuDigit::DoubleMinus => -2,
-            SnafuDigit::Minus => -1,
-            SnafuDigit::Zero => 0,
-            SnafuDigit::One => 1,
-            SnafuDigit::Two => 2,
-        }
-    }
-}
-
-struct Snafu {
-    // Digits stored from least-significant to most-significant digit.
-    digits: Vec<SnafuDigit>,
-}
-
-impl Snafu {
-    fn to_i64(&self) -> i64 {
-        self.digits
-            .iter()
-            .enumerate()
-            .fold(0, |acc, (position, digit)| {
-                acc + 5i64.pow(position as u32) * digit.to_i64()
-            })
-    }
-
-    fn from_i64(mut x: i64) -> Self {
-        let mut digits = vec![];
-        while x != 0 {
-            let current = x % 5;
-            let digit = match current {
-                0 => SnafuDigit::Zero,
-                1 => SnafuDigit::One,
-                2 => SnafuDigit::Two,
-                3 => SnafuDigit::DoubleMinus,
-                4 => SnafuDigit::Minus,
-                _ => panic!(),
-            };
-            x = (x - digit.to_i64()) / 5;
-            digits.push(digit);
-        }
-        Self { digits }
-    }
-}
-
-impl FromStr for Snafu {
-    type Err = Oops;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let digits = s
-            .chars()
-            .rev()
-            .map(|c| {
-                Ok::<_, Oops>(match c {
-                    '=' => SnafuDigit::DoubleMinus,
-                    '-' => SnafuDigit::Minus,
-                    '0' => SnafuDigit::Zero,
-                    '1' => SnafuDigit::One,
-                    '2' => SnafuDigit::Two,
-                    _ => Err(oops!("bad digit {c}"))?,
-                })
-            })
-            .collect::<Result<_, _>>()?;
-        Ok(Snafu { digits })
-    }
-}
-
-impl ToString for Snafu {
-    fn to_string(&self) -> String {
-        self.digits
-            .iter()
-            .rev()
-            .map(|digit| match digit {
-                SnafuDigit::DoubleMinus => '=',
-                SnafuDigit::Minus => '-',
-                SnafuDigit::Zero => '0',
-                SnafuDigit::One => '1',
-                SnafuDigit::Two => '2',
-            })
-            .collect::<String>()
-    }
-}
-
-impl FromStr for Puzzle {
-    type Err = Oops;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Puzzle {
-            values: s.lines().map(str::parse).collect::<Result<Vec<_>, _>>()?,
-        })
-    }
-}
-
-fn parse(input: &str) -> Result<Puzzle, Oops> {
-    input.parse()
-}
-
-fn part1(puzzle: &Puzzle) -> String {
-    Snafu::from_i64(puzzle.values.iter().map(|x| x.to_i64()).sum()).to_string()
-}
-
-fn part2(_puzzle: &Puzzle) -> usize {
-    0
-}
+use aoc_2022::days::day25::Day25;
+use aoc_2022::oops::Oops;
+use aoc_2022::solution;
 
 fn main() -> Result<(), Oops> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-    let input = input;
-
-    let puzzle = parse(&input)?;
-
-    println!("{}", part1(&puzzle));
-    println!("{}", part2(&puzzle));
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const SAMPLE: &str = concat!(
-        "1=-0-2\n", "12111\n", "2=0=\n", "21\n", "2=01\n", "111\n", "20012\n", "112\n", "1=-1=\n",
-        "1-12\n", "12\n", "1=\n", "122\n",
-    );
-
-    #[test]
-    fn snafu_to_i64() {
-        assert_eq!(
-            4890i64,
-            parse(SAMPLE)
-                .unwrap()
-                .values
-                .iter()
-                .map(Snafu::to_i64)
-                .sum()
-        );
-    }
-
-    #[test]
-    fn example1() {
-        assert_eq!("2=-1=0", part1(&parse(SAMPLE).unwrap()));
-    }
-
-    #[test]
-    fn example2() {
-        assert_eq!(0, part2(&parse(SAMPLE).unwrap()));
-    }
+    solution::run::<Day25>(25)
 }
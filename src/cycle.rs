@@ -0,0 +1,406 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// The shape of a repeating sequence: the first `start` steps are a prefix that never repeats, and
+// every step from `start` onwards repeats every `length` steps.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+// A quantity tracked alongside each step of a simulation, e.g. the height of a tower, that grows by
+// the same amount every time the simulation goes around a cycle. Unsigned metrics must never
+// decrease.
+pub trait Metric: Copy {
+    #[must_use]
+    fn sum(self, rhs: Self) -> Self;
+    #[must_use]
+    fn difference(self, rhs: Self) -> Self;
+    #[must_use]
+    fn scale(self, n: usize) -> Self;
+}
+
+macro_rules! impl_metric {
+    ($($t:ty),*) => {
+        $(
+            impl Metric for $t {
+                fn sum(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+
+                fn difference(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+
+                fn scale(self, n: usize) -> Self {
+                    self * <$t>::try_from(n).expect("cycle count should fit in the metric")
+                }
+            }
+        )*
+    };
+}
+
+impl_metric!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+impl<T: Metric, const N: usize> Metric for [T; N] {
+    fn sum(self, rhs: Self) -> Self {
+        std::array::from_fn(|i| self[i].sum(rhs[i]))
+    }
+
+    fn difference(self, rhs: Self) -> Self {
+        std::array::from_fn(|i| self[i].difference(rhs[i]))
+    }
+
+    fn scale(self, n: usize) -> Self {
+        self.map(|m| m.scale(n))
+    }
+}
+
+impl<A: Metric, B: Metric> Metric for (A, B) {
+    fn sum(self, rhs: Self) -> Self {
+        (self.0.sum(rhs.0), self.1.sum(rhs.1))
+    }
+
+    fn difference(self, rhs: Self) -> Self {
+        (self.0.difference(rhs.0), self.1.difference(rhs.1))
+    }
+
+    fn scale(self, n: usize) -> Self {
+        (self.0.scale(n), self.1.scale(n))
+    }
+}
+
+// The metric at every step up to the end of the first time around a cycle, which is enough to
+// extrapolate it to any later step.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleMetrics<M> {
+    pub cycle: Cycle,
+    // Indexed by step, with `cycle.start + cycle.length + 1` entries.
+    metrics: Vec<M>,
+}
+
+impl<M: Metric> CycleMetrics<M> {
+    // How much the metric grows each time around the cycle.
+    #[must_use]
+    pub fn delta(&self) -> M {
+        delta(&self.metrics, self.cycle)
+    }
+
+    // The metric at any step.
+    #[must_use]
+    pub fn extrapolate(&self, step: usize) -> M {
+        extrapolate(&self.metrics, self.cycle, step)
+    }
+}
+
+// `metrics` must cover at least the steps up to `cycle.start + cycle.length`.
+fn delta<M: Metric>(metrics: &[M], cycle: Cycle) -> M {
+    metrics[cycle.start + cycle.length].difference(metrics[cycle.start])
+}
+
+fn extrapolate<M: Metric>(metrics: &[M], cycle: Cycle, step: usize) -> M {
+    if let Some(metric) = metrics.get(step) {
+        return *metric;
+    }
+    let cycles = (step - cycle.start) / cycle.length;
+    let offset = (step - cycle.start) % cycle.length;
+    metrics[cycle.start + offset].sum(delta(metrics, cycle).scale(cycles))
+}
+
+// Finds a cycle in a simulation that is fed one step at a time. Each step is identified by a key
+// that must capture everything that determines the following steps: the first repeated key marks
+// the cycle. Steps are numbered from 0 in the order they are pushed.
+pub struct CycleDetector<K, M> {
+    seen: HashMap<K, usize>,
+    metrics: Vec<M>,
+    cycle: Option<Cycle>,
+}
+
+impl<K: Eq + Hash, M: Metric> Default for CycleDetector<K, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, M: Metric> CycleDetector<K, M> {
+    #[must_use]
+    pub fn new() -> Self {
+        CycleDetector {
+            seen: HashMap::new(),
+            metrics: vec![],
+            cycle: None,
+        }
+    }
+
+    // Records the next step, returning the cycle once one has been found. Once a cycle is found,
+    // further steps are ignored.
+    pub fn push(&mut self, key: K, metric: M) -> Option<Cycle> {
+        if self.cycle.is_some() {
+            return self.cycle;
+        }
+        let step = self.metrics.len();
+        self.metrics.push(metric);
+        if let Some(&start) = self.seen.get(&key) {
+            self.cycle = Some(Cycle {
+                start,
+                length: step - start,
+            });
+        } else {
+            self.seen.insert(key, step);
+        }
+        self.cycle
+    }
+
+    #[must_use]
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    // How much the metric grows each time around the cycle.
+    #[must_use]
+    pub fn delta(&self) -> Option<M> {
+        Some(delta(&self.metrics, self.cycle?))
+    }
+
+    // The metric at any step, extrapolated from the cycle if the step has not been pushed. Returns
+    // `None` if the step has not been pushed and no cycle has been found yet.
+    #[must_use]
+    pub fn extrapolate(&self, step: usize) -> Option<M> {
+        match self.cycle {
+            Some(cycle) => Some(extrapolate(&self.metrics, cycle, step)),
+            None => self.metrics.get(step).copied(),
+        }
+    }
+
+    // The detector's findings, once a cycle has been found.
+    #[must_use]
+    pub fn into_metrics(self) -> Option<CycleMetrics<M>> {
+        Some(CycleMetrics {
+            cycle: self.cycle?,
+            metrics: self.metrics,
+        })
+    }
+}
+
+// Floyd's tortoise and hare: finds the cycle in the sequence `start`, `f(start)`, `f(f(start))`,
+// ... using constant memory. The sequence must eventually repeat. Along with the next state, `f`
+// returns how much the metric grows on that step; `initial` is the metric at step 0. Only the
+// metrics up to the end of the first cycle are kept, for extrapolation.
+pub fn floyd<S, M, F>(start: S, initial: M, mut f: F) -> CycleMetrics<M>
+where
+    S: Clone + Eq,
+    M: Metric,
+    F: FnMut(&S) -> (S, M),
+{
+    let mut next = |s: &S| f(s).0;
+    let mut tortoise = next(&start);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        hare = next(&hare);
+    }
+
+    let mut cycle_start = 0;
+    tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+
+    let cycle = Cycle {
+        start: cycle_start,
+        length,
+    };
+    record_metrics(start, initial, f, cycle)
+}
+
+// Brent's algorithm: like `floyd()`, but usually needs fewer calls to `f`.
+pub fn brent<S, M, F>(start: S, initial: M, mut f: F) -> CycleMetrics<M>
+where
+    S: Clone + Eq,
+    M: Metric,
+    F: FnMut(&S) -> (S, M),
+{
+    let mut next = |s: &S| f(s).0;
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = next(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    tortoise = start.clone();
+    hare = start.clone();
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        cycle_start += 1;
+    }
+
+    let cycle = Cycle {
+        start: cycle_start,
+        length,
+    };
+    record_metrics(start, initial, f, cycle)
+}
+
+// Replays the sequence once the cycle is known, accumulating the metric up to the end of the
+// first cycle.
+fn record_metrics<S, M, F>(start: S, initial: M, mut f: F, cycle: Cycle) -> CycleMetrics<M>
+where
+    M: Metric,
+    F: FnMut(&S) -> (S, M),
+{
+    let mut metrics = Vec::with_capacity(cycle.start + cycle.length + 1);
+    metrics.push(initial);
+    let (mut state, mut metric) = (start, initial);
+    for _ in 0..cycle.start + cycle.length {
+        let (next, gain) = f(&state);
+        metric = metric.sum(gain);
+        metrics.push(metric);
+        state = next;
+    }
+    CycleMetrics { cycle, metrics }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, then 3, 4, 5, 6, 7 repeating forever.
+    fn next(n: &u32) -> u32 {
+        if *n == 7 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    // Steps through `next`, growing the metric by the new state.
+    fn step(n: &u32) -> (u32, u32) {
+        let n = next(n);
+        (n, n)
+    }
+
+    #[test]
+    fn floyd_and_brent() {
+        let expected = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(expected, floyd(0, 0, step).cycle);
+        assert_eq!(expected, brent(0, 0, step).cycle);
+        let constant = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(constant, floyd(0, 0, |n| (*n, 1)).cycle);
+        assert_eq!(constant, brent(0, 0, |n| (*n, 1)).cycle);
+    }
+
+    #[test]
+    fn floyd_and_brent_extrapolate() {
+        for found in [floyd(0, 0, step), brent(0, 0, step)] {
+            assert_eq!(25, found.delta());
+            let mut state = 0;
+            let mut total = 0;
+            for step in 0..100 {
+                total += state;
+                assert_eq!(total, found.extrapolate(step));
+                state = next(&state);
+            }
+        }
+        assert_eq!(1_000_000, floyd(0, 0, |n| (*n, 1)).extrapolate(1_000_000));
+    }
+
+    #[test]
+    fn detector() {
+        let mut detector = CycleDetector::new();
+        let mut state = 0;
+        let mut total = 0;
+        let mut step = 0;
+        let cycle = loop {
+            total += state;
+            if let Some(cycle) = detector.push(state, total) {
+                break cycle;
+            }
+            state = next(&state);
+            step += 1;
+        };
+        assert_eq!(8, step);
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 5
+            },
+            cycle
+        );
+        assert_eq!(Some(25), detector.delta());
+
+        // Check the extrapolation against actually running the sequence.
+        let mut state = 0;
+        let mut total = 0;
+        for step in 0..100 {
+            total += state;
+            assert_eq!(Some(total), detector.extrapolate(step));
+            state = next(&state);
+        }
+    }
+
+    #[test]
+    fn detector_with_several_metrics() {
+        let mut detector = CycleDetector::new();
+        for step in 0..10i64 {
+            if detector.push(step % 4, (step, [2 * step, -step])).is_some() {
+                break;
+            }
+        }
+        assert_eq!(Some((4, [8, -4])), detector.delta());
+        assert_eq!(
+            Some((1_000_000, [2_000_000, -1_000_000])),
+            detector.extrapolate(1_000_000)
+        );
+        assert_eq!(None, CycleDetector::<u8, u32>::new().extrapolate(0));
+        assert_eq!(
+            Some(2_000_000),
+            detector
+                .into_metrics()
+                .map(|m| m.extrapolate(1_000_000).1[0])
+        );
+    }
+}
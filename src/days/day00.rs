@@ -0,0 +1,87 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::oops::Oops;
+use crate::solution::Solution;
+use std::str::FromStr;
+
+pub struct Puzzle {
+    values: Vec<usize>,
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {
+            values: s.lines().map(str::parse).collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle) -> usize {
+    puzzle.values.iter().sum()
+}
+
+fn part2(puzzle: &Puzzle) -> usize {
+    *puzzle.values.iter().max().unwrap()
+}
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        // First line!
+        "123456789\n",
+        // Second line!
+        "987654321\n",
+        // Third line!
+        "2468013579\n"
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(3579124689, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(2468013579, part2(&parse(SAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,109 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::itertools::StrTools;
+use crate::solution::Solution;
+use crate::{oops, oops::Oops};
+use std::collections::BTreeSet;
+
+// Would be nice if rustfmt sorted this...
+#[derive(Ord, Eq, PartialOrd, PartialEq)]
+struct Elf {
+    total_rations: u32,
+}
+
+impl Elf {
+    fn new(total_rations: u32) -> Elf {
+        Elf { total_rations }
+    }
+}
+
+pub struct Puzzle {
+    elves: BTreeSet<Elf>,
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    Ok(Puzzle {
+        elves: input
+            .paragraphs()
+            .map(|group| {
+                Ok::<_, Oops>(Elf::new(
+                    group
+                        .lines()
+                        .map(|line| line.parse::<u32>())
+                        .sum::<Result<_, _>>()?,
+                ))
+            })
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn part1(puzzle: &Puzzle) -> Result<u32, Oops> {
+    puzzle
+        .elves
+        .iter()
+        .last()
+        .map(|elf| elf.total_rations)
+        .ok_or_else(|| oops!("no elves"))
+}
+
+fn part2(puzzle: &Puzzle) -> u32 {
+    puzzle
+        .elves
+        .iter()
+        .rev()
+        .take(3)
+        .map(|elf| elf.total_rations)
+        .sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Puzzle = Puzzle;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<u32, Oops> {
+        part1(puzzle)
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<u32, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "1000\n", "2000\n", "3000\n", "\n", "4000\n", "\n", "5000\n", "6000\n", "\n", "7000\n",
+        "8000\n", "9000\n", "\n", "10000\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(24000, part1(&parse(SAMPLE).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(45000, part2(&parse(SAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,178 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops};
+use std::str::FromStr;
+
+#[derive(Copy, Clone, PartialEq)]
+enum HandShape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl FromStr for HandShape {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" | "X" => Ok(HandShape::Rock),
+            "B" | "Y" => Ok(HandShape::Paper),
+            "C" | "Z" => Ok(HandShape::Scissors),
+            _ => Err("non-matching symbol"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl FromStr for Outcome {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Outcome::Loss),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err("non-matching symbol"),
+        }
+    }
+}
+
+pub struct ParsedLine {
+    opponent_shape: HandShape,
+    my_shape: HandShape,
+    outcome: Outcome,
+}
+
+impl FromStr for ParsedLine {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        if parts.len() != 2 {
+            return Err("each line must have 2 parts");
+        }
+        Ok(ParsedLine {
+            opponent_shape: parts[0].parse::<HandShape>()?,
+            my_shape: parts[1].parse::<HandShape>()?,
+            outcome: parts[1].parse::<Outcome>()?,
+        })
+    }
+}
+
+fn determine_outcome(x: &ParsedLine) -> Outcome {
+    match (x.opponent_shape, x.my_shape) {
+        (HandShape::Rock, HandShape::Paper)
+        | (HandShape::Paper, HandShape::Scissors)
+        | (HandShape::Scissors, HandShape::Rock) => Outcome::Win,
+        (a, b) if a == b => Outcome::Draw,
+        _ => Outcome::Loss,
+    }
+}
+
+fn determine_shape_score(x: HandShape) -> u32 {
+    match x {
+        HandShape::Rock => 1,
+        HandShape::Paper => 2,
+        HandShape::Scissors => 3,
+    }
+}
+
+fn determine_outcome_score(x: Outcome) -> u32 {
+    match x {
+        Outcome::Loss => 0,
+        Outcome::Draw => 3,
+        Outcome::Win => 6,
+    }
+}
+
+fn determine_shape_from_outcome(x: &ParsedLine) -> HandShape {
+    match (x.opponent_shape, x.outcome) {
+        (x, Outcome::Draw) => x,
+        (HandShape::Rock, Outcome::Win) | (HandShape::Scissors, Outcome::Loss) => HandShape::Paper,
+        (HandShape::Paper, Outcome::Win) | (HandShape::Rock, Outcome::Loss) => HandShape::Scissors,
+        (HandShape::Scissors, Outcome::Win) | (HandShape::Paper, Outcome::Loss) => HandShape::Rock,
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<ParsedLine>, Oops> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e| oops!("{e}")).at_line(i + 1, line))
+        .collect()
+}
+
+fn part1(parsed_lines: &[ParsedLine]) -> u32 {
+    parsed_lines
+        .iter()
+        .map(|parsed_line| {
+            determine_outcome_score(determine_outcome(parsed_line))
+                + determine_shape_score(parsed_line.my_shape)
+        })
+        .sum()
+}
+
+fn part2(parsed_lines: &[ParsedLine]) -> u32 {
+    parsed_lines
+        .iter()
+        .map(|parsed_line| {
+            determine_shape_score(determine_shape_from_outcome(parsed_line))
+                + determine_outcome_score(parsed_line.outcome)
+        })
+        .sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Puzzle = Vec<ParsedLine>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<ParsedLine>, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Vec<ParsedLine>) -> Result<u32, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Vec<ParsedLine>) -> Result<u32, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!("A Y\n", "B X\n", "C Z\n");
+
+    #[test]
+    fn example1() {
+        assert_eq!(15, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(12, part2(&parse(SAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,171 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::itertools::{IterTools, StrTools};
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops, scan};
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+struct Stack {
+    crates: Vec<char>,
+}
+
+impl Stack {
+    fn new() -> Stack {
+        Stack { crates: Vec::new() }
+    }
+}
+
+#[derive(Debug)]
+struct Move {
+    count: usize,
+    src: usize,
+    dst: usize,
+}
+
+impl FromStr for Move {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (count, src, dst) = scan!(s, "move {} from {} to {}", usize, usize, usize)?;
+        Ok(Move { count, src, dst })
+    }
+}
+
+pub struct Entity {
+    stacks: Vec<Stack>,
+    moves: Vec<Move>,
+}
+
+fn parse(input: &str) -> Result<Entity, Oops> {
+    // The blank line delimits the crate stack diagram and the move list.
+    let [(_, diagram), (first_move_line, move_lines)] = input
+        .numbered_paragraphs()
+        .collect_array()
+        .context("expected a crate diagram and moves")?;
+    let (crate_lines, labels) = diagram
+        .rsplit_once('\n')
+        .ok_or_else(|| oops!("no crate stacks"))?;
+
+    // The last number on the label line is the number of crate stacks.
+    let stack_count = labels
+        .split_whitespace()
+        .next_back()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let mut stacks: Vec<Stack> = Vec::new();
+    stacks.resize_with(stack_count, Stack::new);
+
+    // Now parse the crate stacks. Scan for alphanumerics; dividing the index by 4 yields the stack
+    // index (0-based). Iterate in reverse to build the stack from the bottom up.
+    for line in crate_lines.lines().rev() {
+        // Another approach is to use chunks() rather than scanning for the alphabetic characters.
+        for (i, c) in line.char_indices().filter(|(_, c)| c.is_alphabetic()) {
+            stacks[i / 4].crates.push(c);
+        }
+    }
+
+    let moves = move_lines
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse::<Move>().at_line(first_move_line + i, line))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Entity { stacks, moves })
+}
+
+fn part1(e: &Entity) -> String {
+    let mut new_stacks = e.stacks.clone();
+    for m in &e.moves {
+        let src = &mut new_stacks[m.src - 1].crates;
+        let mut moved_crates = src.drain(src.len() - m.count..).rev().collect();
+        new_stacks[m.dst - 1].crates.append(&mut moved_crates);
+    }
+
+    new_stacks
+        .iter()
+        .map(|s| *s.crates.last().unwrap())
+        .collect()
+}
+
+fn part2(e: &Entity) -> String {
+    let mut new_stacks = e.stacks.clone();
+    for m in &e.moves {
+        let src = &mut new_stacks[m.src - 1].crates;
+        let mut moved_crates = src.drain(src.len() - m.count..).collect();
+        new_stacks[m.dst - 1].crates.append(&mut moved_crates);
+    }
+
+    new_stacks
+        .iter()
+        .map(|s| *s.crates.last().unwrap())
+        .collect()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Puzzle = Entity;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Entity, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Entity) -> Result<String, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Entity) -> Result<String, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!("CMZ", part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!("MCD", part2(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn bad_move_line() {
+        let input = format!("\n\n{}", SAMPLE.replace("move 3", "move three"));
+        let e = parse(&input).err().unwrap();
+        assert_eq!(Some(9), e.location().line);
+    }
+}
@@ -0,0 +1,86 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::itertools::IterTools;
+use crate::oops::Oops;
+use crate::solution::Solution;
+
+fn get_chars_to_n_unique<const N: usize>(s: &str) -> Result<usize, Oops> {
+    s.chars()
+        .tuple_windows::<N>()?
+        .position(|window| window.iter().all_unique())
+        .map(|i| i + N)
+        .ok_or_else(|| crate::oops!("no answer"))
+}
+
+fn part1(s: &str) -> Result<usize, Oops> {
+    get_chars_to_n_unique::<4>(s)
+}
+
+fn part2(s: &str) -> Result<usize, Oops> {
+    get_chars_to_n_unique::<14>(s)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Puzzle = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<String, Oops> {
+        input
+            .lines()
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| crate::oops!("no input"))
+    }
+
+    fn part1(puzzle: &String) -> Result<usize, Oops> {
+        part1(puzzle)
+    }
+
+    fn part2(puzzle: &String) -> Result<usize, Oops> {
+        part2(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE1: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    const SAMPLE2: &str = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+    const SAMPLE3: &str = "nppdvjthqldpwncqszvftbrmjlhg";
+    const SAMPLE4: &str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+    const SAMPLE5: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+
+    #[test]
+    fn example1() {
+        assert_eq!(7, part1(SAMPLE1).unwrap());
+        assert_eq!(5, part1(SAMPLE2).unwrap());
+        assert_eq!(6, part1(SAMPLE3).unwrap());
+        assert_eq!(10, part1(SAMPLE4).unwrap());
+        assert_eq!(11, part1(SAMPLE5).unwrap());
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(19, part2(SAMPLE1).unwrap());
+        assert_eq!(23, part2(SAMPLE2).unwrap());
+        assert_eq!(23, part2(SAMPLE3).unwrap());
+        assert_eq!(29, part2(SAMPLE4).unwrap());
+        assert_eq!(26, part2(SAMPLE5).unwrap());
+    }
+}
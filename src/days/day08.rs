@@ -0,0 +1,146 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Grid, Point2, Vector2};
+use crate::solution::Solution;
+use crate::{oops, oops::Oops};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+pub struct Puzzle {
+    trees: Grid<i32>,
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {
+            trees: Grid::parse(s, |c| {
+                c.to_digit(10)
+                    .map(|d| d as i32)
+                    .ok_or_else(|| oops!("bad tree height {c}"))
+            })?,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle) -> usize {
+    let trees = &puzzle.trees;
+    let mut visible = HashSet::new();
+
+    for x in 0..trees.width() {
+        let mut counter = |max, (y, tree): (usize, &i32)| {
+            if *tree <= max {
+                return max;
+            }
+            visible.insert(Point2::new(x, y as i32));
+            *tree
+        };
+        trees.col(x).enumerate().fold(-1, &mut counter);
+        trees.col(x).enumerate().rev().fold(-1, &mut counter);
+    }
+
+    for y in 0..trees.height() {
+        let mut counter = |max, (x, tree): (usize, &i32)| {
+            if *tree <= max {
+                return max;
+            }
+            visible.insert(Point2::new(x as i32, y));
+            *tree
+        };
+        trees.row(y).enumerate().fold(-1, &mut counter);
+        trees.row(y).enumerate().rev().fold(-1, &mut counter);
+    }
+
+    visible.len()
+}
+
+// Counts the trees visible from `p` looking in direction `v`, up to and including the first tree
+// that is at least as tall as the one at `p`.
+fn viewing_distance(trees: &Grid<i32>, p: Point2, v: Vector2) -> usize {
+    let height = trees[p];
+    let mut distance = 0;
+    let mut next = p + v;
+    while let Some(&tree) = trees.get(next) {
+        distance += 1;
+        if tree >= height {
+            break;
+        }
+        next += v;
+    }
+    distance
+}
+
+fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+    const DIRECTIONS: [Vector2; 4] = [
+        Vector2::new(-1, 0),
+        Vector2::new(1, 0),
+        Vector2::new(0, -1),
+        Vector2::new(0, 1),
+    ];
+
+    puzzle
+        .trees
+        .points()
+        .map(|p| {
+            DIRECTIONS
+                .iter()
+                .map(|&v| viewing_distance(&puzzle.trees, p, v))
+                .product()
+        })
+        .max()
+        .ok_or_else(|| oops!("no trees"))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        part2(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!("30373\n", "25512\n", "65332\n", "33549\n", "35390\n",);
+
+    #[test]
+    fn example1() {
+        assert_eq!(21, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(8, part2(&parse(SAMPLE).unwrap()).unwrap());
+    }
+}
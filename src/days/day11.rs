@@ -0,0 +1,259 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::itertools::{IterTools, StrTools};
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops, scan};
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+#[derive(Clone, Copy)]
+enum Operand {
+    Literal(usize),
+    Old,
+}
+
+impl FromStr for Operand {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "old" => Operand::Old,
+            s => Operand::Literal(s.parse()?),
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add,
+    Multiply,
+}
+
+impl FromStr for Op {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "+" => Op::Add,
+            "*" => Op::Multiply,
+            _ => Err(oops!("bad op {s}"))?,
+        })
+    }
+}
+
+#[derive(Clone)]
+struct Monkey {
+    items: VecDeque<usize>,
+    op: Op,
+    operand: Operand,
+    divisor_test: usize,
+    on_true: usize,
+    on_false: usize,
+}
+
+impl FromStr for Monkey {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Skip monkey index.
+        let mut parser = s.lines().skip(1).map(str::trim);
+        let mut next_line = |what| parser.next().ok_or_else(|| oops!("no {what}"));
+        let [items] = scan::scan("Starting items: {}", next_line("starting items")?)?;
+        let items = scan::integers(items.text)
+            .collect::<Result<_, _>>()
+            .context("while parsing starting items")?;
+        let (op, operand) = scan!(
+            next_line("operation")?,
+            "Operation: new = old {} {}",
+            Op,
+            Operand
+        )
+        .context("while parsing operation")?;
+        let [divisor_test] = scan::integers(next_line("test")?)
+            .exactly()
+            .context("while parsing test")?;
+        let [on_true] = scan::integers(next_line("if true")?)
+            .exactly()
+            .context("while parsing if true")?;
+        let [on_false] = scan::integers(next_line("if false")?)
+            .exactly()
+            .context("while parsing if false")?;
+        Ok(Monkey {
+            items,
+            op,
+            operand,
+            divisor_test,
+            on_true,
+            on_false,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Puzzle {
+    monkeys: Vec<Monkey>,
+}
+
+impl Puzzle {
+    fn calculate_mbl<F: Fn(usize) -> usize>(
+        &mut self,
+        rounds: usize,
+        mitigate_worry: &F,
+    ) -> Result<usize, Oops> {
+        let inspections = (0..rounds).fold(vec![0; self.monkeys.len()], |mut acc, _| {
+            for (i, acc) in acc.iter_mut().enumerate() {
+                *acc += self.monkeys[i].items.len();
+                self.process_monkey(i, mitigate_worry);
+            }
+            acc
+        });
+
+        Ok(inspections.into_iter().k_largest(2)?.iter().product())
+    }
+
+    fn process_monkey<F: Fn(usize) -> usize>(&mut self, i: usize, mitigate_worry: &F) {
+        let op = self.monkeys[i].op;
+        let operand = self.monkeys[i].operand;
+        let apply_op = |current_worry| {
+            let operand = match operand {
+                Operand::Literal(x) => x,
+                Operand::Old => current_worry,
+            };
+            match op {
+                Op::Add => current_worry + operand,
+                Op::Multiply => current_worry * operand,
+            }
+        };
+
+        while let Some(worry_level) = self.monkeys[i].items.pop_front() {
+            let worry_level = mitigate_worry(apply_op(worry_level));
+            let target = if worry_level.is_multiple_of(self.monkeys[i].divisor_test) {
+                self.monkeys[i].on_true
+            } else {
+                self.monkeys[i].on_false
+            };
+            self.monkeys[target].items.push_back(worry_level);
+        }
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let monkeys = s
+            .paragraphs()
+            .enumerate()
+            .map(|(i, monkey)| {
+                monkey
+                    .parse()
+                    .with_context(|| format!("while parsing monkey {i}"))
+            })
+            .collect::<Result<Vec<Monkey>, _>>()?;
+        Ok(Puzzle { monkeys })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+    let mut puzzle = (*puzzle).clone();
+    let mitigate_worry = |x| x / 3;
+    puzzle.calculate_mbl(20, &mitigate_worry)
+}
+
+fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+    let mut puzzle = (*puzzle).clone();
+    let factor: usize = puzzle.monkeys.iter().map(|x| x.divisor_test).product();
+    let mitigate_worry = |x| x % factor;
+    puzzle.calculate_mbl(10000, &mitigate_worry)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        part1(puzzle)
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        part2(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "Monkey 0:\n",
+        "  Starting items: 79, 98\n",
+        "  Operation: new = old * 19\n",
+        "  Test: divisible by 23\n",
+        "    If true: throw to monkey 2\n",
+        "    If false: throw to monkey 3\n",
+        "\n",
+        "Monkey 1:\n",
+        "  Starting items: 54, 65, 75, 74\n",
+        "  Operation: new = old + 6\n",
+        "  Test: divisible by 19\n",
+        "    If true: throw to monkey 2\n",
+        "    If false: throw to monkey 0\n",
+        "\n",
+        "Monkey 2:\n",
+        "  Starting items: 79, 60, 97\n",
+        "  Operation: new = old * old\n",
+        "  Test: divisible by 13\n",
+        "    If true: throw to monkey 1\n",
+        "    If false: throw to monkey 3\n",
+        "\n",
+        "Monkey 3:\n",
+        "  Starting items: 74\n",
+        "  Operation: new = old + 3\n",
+        "  Test: divisible by 17\n",
+        "    If true: throw to monkey 0\n",
+        "    If false: throw to monkey 1\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(10605, part1(&parse(SAMPLE).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(2713310158, part2(&parse(SAMPLE).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn bad_operator() {
+        let input = SAMPLE.replace("old + 6", "old - 6");
+        let e = parse(&input).err().unwrap();
+        assert_eq!(
+            vec!["while parsing operation", "while parsing monkey 1"],
+            e.frames()
+        );
+    }
+}
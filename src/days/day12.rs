@@ -0,0 +1,129 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Grid, Point2};
+use crate::search;
+use crate::solution::Solution;
+use crate::{oops, oops::Oops};
+use std::str::FromStr;
+
+pub struct Puzzle {
+    map: Grid<i32>,
+    start: Point2,
+    end: Point2,
+}
+
+impl Puzzle {
+    // The fewest steps from any of `starts` to the end, climbing at most one unit of height per
+    // step.
+    fn bfs(&self, starts: impl IntoIterator<Item = Point2>) -> Option<usize> {
+        search::bfs(
+            starts,
+            |&p| {
+                let height = self.map[p];
+                self.map
+                    .neighbors(p)
+                    .filter(move |(_, h)| *h - height <= 1)
+                    .map(|(n, _)| n)
+            },
+            |p| *p == self.end,
+        )
+        .cost()
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let squares = Grid::parse(s, |c| match c {
+            'S' | 'E' | 'a'..='z' => Ok(c),
+            _ => Err(oops!("bad square {c}")),
+        })?;
+        Ok(Puzzle {
+            map: squares.map(|&c| match c {
+                'S' => 0,
+                'E' => 25,
+                c => c as i32 - 'a' as i32,
+            }),
+            start: squares
+                .position(|&c| c == 'S')
+                .ok_or_else(|| oops!("no start"))?,
+            end: squares
+                .position(|&c| c == 'E')
+                .ok_or_else(|| oops!("no end"))?,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+    puzzle
+        .bfs([puzzle.start])
+        .ok_or_else(|| oops!("no solution"))
+}
+
+fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+    let starts = puzzle
+        .map
+        .iter()
+        .filter_map(|(p, height)| if *height == 0 { Some(p) } else { None });
+    puzzle.bfs(starts).ok_or_else(|| oops!("no solution"))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        part1(puzzle)
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        part2(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "Sabqponm\n",
+        "abcryxxl\n",
+        "accszExk\n",
+        "acctuvwj\n",
+        "abdefghi\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(31, part1(&parse(SAMPLE).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(29, part2(&parse(SAMPLE).unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,187 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Point2, SparseGrid};
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops};
+use std::str::FromStr;
+
+// Cells that are not in the grid are air.
+#[derive(Clone, Copy)]
+enum Material {
+    Rock,
+    Sand,
+    Source,
+}
+
+#[derive(Clone)]
+pub struct Puzzle {
+    grid: SparseGrid<Material>,
+}
+
+impl Puzzle {
+    fn draw_line(&mut self, p1: &Point2, p2: &Point2, m: Material) {
+        let x1 = std::cmp::min(p1.x, p2.x);
+        let x2 = std::cmp::max(p1.x, p2.x);
+        let y1 = std::cmp::min(p1.y, p2.y);
+        let y2 = std::cmp::max(p1.y, p2.y);
+
+        // This assumes the lines are always strictly horizontal or strictly vertical.
+        for x in x1..=x2 {
+            for y in y1..=y2 {
+                self.grid.insert(Point2::new(x, y), m);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rendered = self.grid.render(
+            |m| match m {
+                Material::Rock => '#',
+                Material::Sand => 'O',
+                Material::Source => '+',
+            },
+            '.',
+        );
+        writeln!(f, "{rendered}")
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut puzzle = Puzzle {
+            grid: SparseGrid::new(),
+        };
+        for (i, line) in s.lines().enumerate() {
+            let mut prev_pt = None;
+            for point in line.split(" -> ") {
+                let cur_pt = point
+                    .split_once(',')
+                    .ok_or_else(|| oops!("invalid point {point}"))
+                    .and_then(|(x, y)| Ok(Point2::new(x.parse()?, y.parse()?)))
+                    .at_line(i + 1, line)?;
+                if let Some(prev_pt) = prev_pt {
+                    puzzle.draw_line(&prev_pt, &cur_pt, Material::Rock);
+                }
+                prev_pt = Some(cur_pt);
+            }
+        }
+        // Per the problem, (500, 0) is the source of sand.
+        puzzle.grid.insert(Point2::new(500, 0), Material::Source);
+        Ok(puzzle)
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn drop_sand(puzzle: &Puzzle, position: &Point2) -> Option<Point2> {
+    // The bounds cover all the rock, so sand that reaches the bottom row falls forever. Parsing
+    // always inserts the source, so the grid cannot be empty.
+    let bottom = puzzle.grid.bounds().unwrap().max.y;
+    let mut current = *position;
+    'falling: while current.y < bottom {
+        for dx in [0, -1, 1] {
+            let next = Point2::new(current.x + dx, current.y + 1);
+            if !puzzle.grid.contains(next) {
+                current = next;
+                continue 'falling;
+            }
+        }
+        if current == *position {
+            return None;
+        }
+        return Some(current);
+    }
+    None
+}
+
+fn part1(puzzle: &Puzzle) -> usize {
+    let mut puzzle = puzzle.clone();
+    loop {
+        for x in 0.. {
+            let Some(position) = drop_sand(&puzzle, &Point2::new(500, 0)) else {
+                return x;
+            };
+            puzzle.grid.insert(position, Material::Sand);
+        }
+    }
+}
+
+fn part2(puzzle: &Puzzle) -> usize {
+    let mut puzzle = puzzle.clone();
+    // Parsing always inserts the source, so the grid cannot be empty.
+    let bounds = puzzle.grid.bounds().unwrap();
+    let bottom = bounds.max.y + 2;
+    let height = bottom - bounds.min.y;
+    puzzle.draw_line(
+        &Point2::new(500 - height, bottom),
+        &Point2::new(500 + height, bottom),
+        Material::Rock,
+    );
+
+    loop {
+        for x in 1.. {
+            let Some(position) = drop_sand(&puzzle, &Point2::new(500, 0)) else {
+                return x;
+            };
+            puzzle.grid.insert(position, Material::Sand);
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "498,4 -> 498,6 -> 496,6\n",
+        "503,4 -> 502,4 -> 502,9 -> 494,9\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(24, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(93, part2(&parse(SAMPLE).unwrap()));
+    }
+}
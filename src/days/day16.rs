@@ -0,0 +1,416 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::parallel;
+use crate::search::{self, DistanceMatrix};
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops, scan};
+use std::collections::HashMap;
+use std::ops::BitOr;
+use std::str::FromStr;
+
+// A valve in the contracted graph, represented as a single bit so sets of valves fit in a u64. The
+// starting valve is always `Label(1)`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Label(u64);
+
+impl Label {
+    fn from_id(id: usize) -> Self {
+        Label(1 << id)
+    }
+
+    fn id(self) -> usize {
+        self.0.trailing_zeros() as usize
+    }
+}
+
+#[derive(Debug)]
+struct Valve<'a> {
+    flow: i32,
+    next: Vec<&'a str>,
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    // Indexed by label id. Only the starting valve and valves with a nonzero flow are kept.
+    flows: Vec<i32>,
+    distances: DistanceMatrix,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Goal {
+    valve: Label,
+    left: i32,
+}
+
+impl Goal {
+    fn new(valve: Label, left: i32) -> Self {
+        Goal { valve, left }
+    }
+
+    fn set_new_target(&self, p: &Puzzle, valve: Label) -> Self {
+        Goal::new(valve, p.distance_between(self.valve, valve) + 1)
+    }
+
+    fn next(&self, x: i32) -> Self {
+        assert!(self.left >= x);
+        Goal::new(self.valve, self.left - x)
+    }
+}
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+struct LabelSet(u64);
+
+impl BitOr<Label> for LabelSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Label) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+fn labels_to_set(labels: &[Label]) -> LabelSet {
+    labels.iter().fold(LabelSet(0), |acc, &label| acc | label)
+}
+
+// The most pressure found so far to be relieved from each set of unvisited valves, used to prune
+// the search.
+type BestSeen = HashMap<LabelSet, i32>;
+
+impl Puzzle {
+    fn find_path<const N: usize>(
+        &self,
+        targets: &mut Vec<Label>,
+        assigned: usize,
+        best_seen: &mut BestSeen,
+        goals: &[Goal; N],
+        so_far: i32,
+        remaining_time: i32,
+    ) -> i32 {
+        // Invariant: when calling this function, at least one goal must have been reached, i.e.
+        // goal.left == 0.
+
+        // All valves have been visited.
+        if assigned == targets.len() {
+            return so_far;
+        }
+
+        // Calculate an upper bound for the maximum possible pressure that can still be relieved.
+        let max_possible_remaining = &targets[assigned..]
+            .iter()
+            .map(|r| {
+                let min_time = goals
+                    .iter()
+                    .map(|g| g.left + self.distance_between(g.valve, *r) + 1)
+                    .min()
+                    .unwrap();
+                std::cmp::max(remaining_time - min_time, 0) * self.flow_for(*r)
+            })
+            .sum();
+
+        let best_seen_key = labels_to_set(&targets[assigned..]);
+        if let Some(&best_seen) = best_seen.get(&best_seen_key) {
+            if best_seen > max_possible_remaining + so_far {
+                // There is already another path that uses the remaining unassigned valves in a
+                // more efficient way: no need to waste more time exploring this branch.
+                return 0;
+            }
+        }
+
+        let mut best = so_far;
+
+        // targets[..assigned] have been visited and activated. Permute through all possible
+        // remaining combinations of targets[assigned..].
+        for x in assigned..targets.len() {
+            targets.swap(x, assigned);
+            if let Some(result) =
+                self.visit_next(targets, assigned, best_seen, goals, so_far, remaining_time)
+            {
+                best = std::cmp::max(best, result);
+            }
+            targets.swap(x, assigned);
+        }
+
+        best_seen
+            .entry(best_seen_key)
+            .and_modify(|current| *current = std::cmp::max(*current, best))
+            .or_insert(best);
+
+        best
+    }
+
+    // Sends the first goal that has been reached to targets[assigned] and continues the search from
+    // there. Returns `None` if the valve cannot be reached in time.
+    fn visit_next<const N: usize>(
+        &self,
+        targets: &mut Vec<Label>,
+        assigned: usize,
+        best_seen: &mut BestSeen,
+        goals: &[Goal; N],
+        so_far: i32,
+        remaining_time: i32,
+    ) -> Option<i32> {
+        let next_goal_idx = goals
+            .iter()
+            .enumerate()
+            .find_map(|(i, g)| if g.left == 0 { Some(i) } else { None })
+            .expect("goal should have been reached");
+
+        let mut goals = *goals;
+        goals[next_goal_idx] = goals[next_goal_idx].set_new_target(self, targets[assigned]);
+        let time_to_next_goal = goals[next_goal_idx].left;
+        if time_to_next_goal > remaining_time {
+            return None;
+        }
+        let advance_by = goals.iter().map(|g| g.left).min().unwrap();
+        for g in goals.iter_mut() {
+            *g = g.next(advance_by);
+        }
+        Some(self.find_path(
+            targets,
+            assigned + 1,
+            best_seen,
+            &goals,
+            so_far + (remaining_time - time_to_next_goal) * self.flow_for(targets[assigned]),
+            remaining_time - advance_by,
+        ))
+    }
+
+    // Same result as `find_path` from the starting position, but each choice of first valve is
+    // explored separately on a pool of threads. Each worker keeps its own `best_seen` table across
+    // the branches it handles, so there is no lock on the hot path. Pruning only discards paths
+    // that cannot beat one already found, so the maximum is the same however branches are split.
+    fn find_path_threaded<const N: usize>(
+        &self,
+        targets: &[Label],
+        goals: &[Goal; N],
+        remaining_time: i32,
+        threads: usize,
+    ) -> i32 {
+        if threads <= 1 {
+            return self.find_path(
+                &mut targets.to_vec(),
+                0,
+                &mut HashMap::new(),
+                goals,
+                0,
+                remaining_time,
+            );
+        }
+        let firsts: Vec<_> = (0..targets.len()).collect();
+        parallel::map_init(&firsts, threads, HashMap::new, |best_seen, &x| {
+            let mut targets = targets.to_vec();
+            targets.swap(x, 0);
+            self.visit_next(&mut targets, 0, best_seen, goals, 0, remaining_time)
+                .unwrap_or(0)
+        })
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+    }
+
+    // Note: this counts physical distance and does not include the time to activate a valve.
+    fn distance_between(&self, from: Label, to: Label) -> i32 {
+        self.distances
+            .get(from.id(), to.id())
+            .expect("parsing checks that every target is reachable") as i32
+    }
+
+    fn flow_for(&self, valve: Label) -> i32 {
+        self.flows[valve.id()]
+    }
+
+    // Every valve worth opening. Only the starting valve may have no flow.
+    fn targets(&self) -> Vec<Label> {
+        (0..self.flows.len())
+            .filter(|id| self.flows[*id] > 0)
+            .map(Label::from_id)
+            .collect()
+    }
+}
+
+fn parse_valve(line: &str) -> Result<(&str, Valve<'_>), Oops> {
+    let [name, flow, tunnels] = scan::scan("Valve {} has flow rate={}; {}", line)?;
+    let valves = tunnels
+        .text
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| tunnels.text.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(|| {
+            oops!("expected tunnels")
+                .at_column(tunnels.column)
+                .with_text(line)
+        })?;
+    Ok((
+        name.text,
+        Valve {
+            flow: flow.parse()?,
+            next: valves.split(", ").collect(),
+        },
+    ))
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valves = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| parse_valve(line).at_line(i + 1, line))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        // Drop any nodes with zero flows, as they will never be targetted, but keep the starting
+        // node as the first, so it gets `Label(1)`. The starting node is still a target if its
+        // flow is nonzero.
+        let mut interesting: Vec<_> = valves
+            .iter()
+            .filter_map(|(name, v)| (v.flow > 0 && *name != "AA").then_some(*name))
+            .collect();
+        interesting.sort_unstable();
+        interesting.insert(0, "AA");
+        if interesting.len() > 64 {
+            return Err(oops!("too many valves with nonzero flow"));
+        }
+        let contracted = search::contract(interesting, |name| {
+            valves
+                .get(name)
+                .map_or(&[][..], |v| &v.next[..])
+                .iter()
+                .copied()
+        });
+        let names = contracted.nodes().to_vec();
+        let flows = names
+            .iter()
+            .map(|name| valves.get(name).map_or(0, |v| v.flow))
+            .collect();
+        let puzzle = Puzzle {
+            flows,
+            distances: contracted.into_distances(),
+        };
+        // The search needs the distance from the start or any target to every target.
+        for from in 0..puzzle.flows.len() {
+            for to in puzzle.targets() {
+                if puzzle.distances.get(from, to.id()).is_none() {
+                    return Err(oops!(
+                        "valve {} cannot reach valve {}",
+                        names[from],
+                        names[to.id()]
+                    ));
+                }
+            }
+        }
+        Ok(puzzle)
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle, threads: usize) -> i32 {
+    puzzle.find_path_threaded(&puzzle.targets(), &[Goal::new(Label(1), 0)], 30, threads)
+}
+
+fn part2(puzzle: &Puzzle, threads: usize) -> i32 {
+    puzzle.find_path_threaded(
+        &puzzle.targets(),
+        &[Goal::new(Label(1), 0), Goal::new(Label(1), 0)],
+        26,
+        threads,
+    )
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Puzzle = Puzzle;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<i32, Oops> {
+        Ok(part1(puzzle, 1))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<i32, Oops> {
+        Ok(part2(puzzle, 1))
+    }
+
+    fn part1_threaded(puzzle: &Puzzle, threads: usize) -> Result<i32, Oops> {
+        Ok(part1(puzzle, threads))
+    }
+
+    fn part2_threaded(puzzle: &Puzzle, threads: usize) -> Result<i32, Oops> {
+        Ok(part2(puzzle, threads))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\n",
+        "Valve BB has flow rate=13; tunnels lead to valves CC, AA\n",
+        "Valve CC has flow rate=2; tunnels lead to valves DD, BB\n",
+        "Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE\n",
+        "Valve EE has flow rate=3; tunnels lead to valves FF, DD\n",
+        "Valve FF has flow rate=0; tunnels lead to valves EE, GG\n",
+        "Valve GG has flow rate=0; tunnels lead to valves FF, HH\n",
+        "Valve HH has flow rate=22; tunnel leads to valve GG\n",
+        "Valve II has flow rate=0; tunnels lead to valves AA, JJ\n",
+        "Valve JJ has flow rate=21; tunnel leads to valve II\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(1651, part1(&parse(SAMPLE).unwrap(), 1));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(1707, part2(&parse(SAMPLE).unwrap(), 1));
+    }
+
+    #[test]
+    fn threaded() {
+        let puzzle = parse(SAMPLE).unwrap();
+        assert_eq!(1651, part1(&puzzle, 4));
+        assert_eq!(1707, part2(&puzzle, 4));
+    }
+
+    #[test]
+    fn start_with_flow() {
+        let puzzle = parse(concat!(
+            "Valve AA has flow rate=10; tunnel leads to valve BB\n",
+            "Valve BB has flow rate=1; tunnel leads to valve AA\n",
+        ))
+        .unwrap();
+        assert_eq!(29 * 10 + 27, part1(&puzzle, 1));
+    }
+
+    #[test]
+    fn unreachable_valve() {
+        let e = parse(concat!(
+            "Valve AA has flow rate=0; tunnel leads to valve BB\n",
+            "Valve BB has flow rate=1; tunnel leads to valve AA\n",
+            "Valve CC has flow rate=2; tunnel leads to valve CC\n",
+        ))
+        .err()
+        .unwrap();
+        assert!(e.to_string().contains("cannot reach valve CC"), "{e}");
+    }
+}
@@ -0,0 +1,335 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::cycle::CycleDetector;
+use crate::geometry::BitGrid;
+use crate::solution::Solution;
+use crate::{oops, oops::Oops};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::str::FromStr;
+
+type Row = u16;
+
+#[derive(Clone, Copy)]
+enum Jet {
+    Left = 0,
+    Right = 1,
+}
+
+pub struct Puzzle {
+    jets: Vec<Jet>,
+}
+
+#[allow(clippy::eq_op, clippy::identity_op)]
+const ROCKS: [u64; 5] = [
+    (0b0000_0000_0000_0000 << 48)
+        | (0b0000_0000_0000_0000 << 32)
+        | (0b0000_0000_0000_0000 << 16)
+        | (0b0001_1110_0000_0000),
+    (0b0000_0000_0000_0000 << 48)
+        | (0b0000_1000_0000_0000 << 32)
+        | (0b0001_1100_0000_0000 << 16)
+        | (0b0000_1000_0000_0000),
+    (0b0000_0000_0000_0000 << 48)
+        | (0b0000_0100_0000_0000 << 32)
+        | (0b0000_0100_0000_0000 << 16)
+        | (0b0001_1100_0000_0000),
+    (0b0001_0000_0000_0000 << 48)
+        | (0b0001_0000_0000_0000 << 32)
+        | (0b0001_0000_0000_0000 << 16)
+        | (0b0001_0000_0000_0000),
+    (0b0000_0000_0000_0000 << 48)
+        | (0b0000_0000_0000_0000 << 32)
+        | (0b0001_1000_0000_0000 << 16)
+        | (0b0001_1000_0000_0000),
+];
+
+const ROCK_HEIGHTS: [usize; ROCKS.len()] = [1, 3, 3, 4, 2];
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {
+            jets: s
+                .trim()
+                .chars()
+                .map(|c| {
+                    Ok::<_, Oops>(match c {
+                        '<' => Jet::Left,
+                        '>' => Jet::Right,
+                        c => Err(oops!("unexpected jet {}", c))?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+// The side walls of every row, which are not stored in the chamber.
+const WALLS: Row = 0x80ff;
+
+// The settled rocks. Bit `x` of a row is column `x` of `rocks`, in the same layout as `ROCKS`, and
+// row `y` is the height above the floor at row 0.
+struct Chamber {
+    rocks: BitGrid,
+    max_height: usize,
+}
+
+impl Chamber {
+    fn new() -> Self {
+        let mut rocks = BitGrid::new();
+        rocks.or_row_window(0, 0, 0xffff);
+        Chamber {
+            rocks,
+            max_height: 0,
+        }
+    }
+
+    fn mark_new_rows_used(&mut self, height: usize) {
+        self.max_height = std::cmp::max(self.max_height, height);
+    }
+
+    // Identifies the shape of the top of the tower, which is all that affects where the next rocks
+    // land. Only valid once the tower is at least 100 rows tall.
+    fn top_rows_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for i in 0..100 {
+            hasher.write_u16(self.row(self.max_height - i));
+        }
+        hasher.finish()
+    }
+
+    fn row(&self, n: usize) -> Row {
+        self.rocks.row_window(n as i32, 0) as Row | WALLS
+    }
+
+    // Settles `rock`, whose lowest row is at height `bottom`.
+    fn add_rock(&mut self, bottom: usize, rock: u64) {
+        for i in 0..4 {
+            self.rocks
+                .or_row_window((bottom + i) as i32, 0, rock >> (16 * i) & 0xffff);
+        }
+    }
+
+    #[allow(dead_code)]
+    fn render(&self) {
+        for i in (0..=self.max_height).rev() {
+            let data = self.row(i);
+            println!(
+                "{i:0>4} {}",
+                (7..16)
+                    .rev()
+                    .map(|pos| if data & (1 << pos) == 0 { '.' } else { '#' })
+                    .collect::<String>()
+            );
+        }
+    }
+}
+
+fn rock_and_jets_to_index(rock_count: usize, jet1: Jet, jet2: Jet, jet3: Jet, jet4: Jet) -> usize {
+    (rock_count << 4)
+        | ((jet1 as usize) << 3)
+        | ((jet2 as usize) << 2)
+        | ((jet3 as usize) << 1)
+        | (jet4 as usize)
+}
+
+// Entries are indexed by `rock_and_jets_to_index()`.
+fn build_new_rock_lookup_table() -> Vec<u64> {
+    let mut table = vec![0; ROCKS.len() * 16];
+    let walls_bitmask: u64 = 0x80ff_80ff_80ff_80ff;
+    for i in 0..ROCKS.len() {
+        for j1 in &[Jet::Left, Jet::Right] {
+            for j2 in &[Jet::Left, Jet::Right] {
+                for j3 in &[Jet::Left, Jet::Right] {
+                    for j4 in &[Jet::Left, Jet::Right] {
+                        let mut rock = ROCKS[i];
+                        // For some inexplicable reason, iterating over &[] instead of [] affects overall
+                        // throughput...
+                        for j in &[j1, j2, j3, j4] {
+                            let next_rock = match j {
+                                Jet::Left => rock << 1,
+                                Jet::Right => rock >> 1,
+                            };
+                            if next_rock & walls_bitmask == 0 {
+                                rock = next_rock;
+                            }
+                        }
+                        table[rock_and_jets_to_index(i, *j1, *j2, *j3, *j4)] = rock;
+                    }
+                }
+            }
+        }
+    }
+    table
+}
+
+fn run_simulation<const MAX_ROCK_COUNT: usize>(puzzle: &Puzzle) -> usize {
+    enum State {
+        NewRock,
+        FallJet,
+    }
+
+    let mut rock_heights = ROCK_HEIGHTS.iter().cycle();
+    let mut jets = puzzle.jets.iter().cycle();
+
+    let mut state = State::NewRock;
+    let mut chamber = Chamber::new();
+
+    let rock_table = build_new_rock_lookup_table();
+    let mut rock_count = 0;
+    let mut current_rock = ROCKS[0];
+    let mut current_rock_height = 0;
+    // Represents the bottom of the current rock.
+    let mut rock_bottom = 1;
+    let mut chamber_rows: u64 = 0;
+    // Jets are pulled from a cycled iterator for speed, so track the position separately.
+    let mut jet_count = 0;
+    let mut cycle_detector = CycleDetector::new();
+    // The number of rocks that had landed at the first step fed to `cycle_detector`.
+    let mut first_rock_count = None;
+    while rock_count < MAX_ROCK_COUNT {
+        match state {
+            State::NewRock => {
+                current_rock = unsafe {
+                    // jets is a cycled iterator that will never return None.
+                    let j1 = jets.next().unwrap_unchecked();
+                    let j2 = jets.next().unwrap_unchecked();
+                    let j3 = jets.next().unwrap_unchecked();
+                    let j4 = jets.next().unwrap_unchecked();
+                    // TODO: better scope unsafeness for rock lookup table.
+                    *rock_table.get_unchecked(rock_and_jets_to_index(
+                        rock_count % ROCKS.len(),
+                        *j1,
+                        *j2,
+                        *j3,
+                        *j4,
+                    ))
+                };
+                // rock_heights is a cycled iterator that will never return None.
+                jet_count += 4;
+                current_rock_height = unsafe { *rock_heights.next().unwrap_unchecked() };
+                rock_bottom = chamber.max_height + 1;
+                // Normally, rocks start at chamber.max_height + 4. However, it is guaranteed that
+                // each rock can shift 4x and fall 3x without hitting anything (other than the side
+                // walls), so the falls can be unconditionally simulated, while a lookup table can
+                // be used for the side walls.
+                state = State::FallJet;
+                rock_count += 1;
+                if rock_count % 100_000_000 == 0 {
+                    // println!("count: {}", rock_count);
+                }
+                if rock_count < 15 {
+                    // chamber.render();
+                }
+                // The new rock always spawns immediately above any non-wall collisions, so the
+                // only bits that are set will be for walls.
+                chamber_rows = 0x80ff_80ff_80ff_80ff;
+                continue;
+            }
+            State::FallJet => {
+                chamber_rows = (chamber_rows << 16) | u64::from(chamber.row(rock_bottom - 1));
+                if (current_rock & chamber_rows) != 0 {
+                    chamber.add_rock(rock_bottom, current_rock);
+                    state = State::NewRock;
+
+                    // rock_bottom is where rocks spawn, which is one above the actual topmost
+                    // rock.
+                    let possible_new_top = rock_bottom + current_rock_height - 1;
+                    chamber.mark_new_rows_used(possible_new_top);
+
+                    if chamber.max_height > 100 {
+                        let first_rock_count = *first_rock_count.get_or_insert(rock_count);
+                        let key = (
+                            rock_count % ROCKS.len(),
+                            jet_count % puzzle.jets.len(),
+                            chamber.top_rows_hash(),
+                        );
+                        if cycle_detector.push(key, chamber.max_height).is_some() {
+                            // The loop stops once MAX_ROCK_COUNT - 1 rocks have landed.
+                            return cycle_detector
+                                .extrapolate(MAX_ROCK_COUNT - 1 - first_rock_count)
+                                .expect("cycle should have been found");
+                        }
+                    }
+
+                    continue;
+                }
+                rock_bottom -= 1;
+                jet_count += 1;
+                // jets is a cycled iterator that will never return None.
+                let shifted_rock = match unsafe { jets.next().unwrap_unchecked() } {
+                    Jet::Left => current_rock << 1,
+                    Jet::Right => current_rock >> 1,
+                };
+                if (shifted_rock & chamber_rows) == 0 {
+                    current_rock = shifted_rock;
+                }
+                continue;
+            }
+        }
+    }
+    chamber.max_height
+}
+
+fn part1(puzzle: &Puzzle) -> usize {
+    run_simulation::<2023>(puzzle)
+}
+
+fn part2(puzzle: &Puzzle) -> usize {
+    run_simulation::<1_000_000_000_001>(puzzle)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn example1() {
+        assert_eq!(3068, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(1514285714288, part2(&parse(SAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,134 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Bounds3, Point3};
+use crate::search;
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+pub struct Puzzle {
+    points: Vec<Point3>,
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {
+            points: s
+                .lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    let mut parser = line.split(',');
+                    let mut next = |axis| {
+                        parser
+                            .next()
+                            .ok_or_else(|| oops!("missing {axis}"))?
+                            .parse::<i32>()
+                            .at_line(i + 1, line)
+                    };
+                    Ok(Point3::new(next("x")?, next("y")?, next("z")?))
+                })
+                .collect::<Result<_, Oops>>()?,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle) -> i32 {
+    let mut counts = HashMap::<Point3, i32>::new();
+    for p in &puzzle.points {
+        let count: i32 = p
+            .neighbors()
+            .map(|neighbor| {
+                if let Some(neighbor_count) = counts.get_mut(&neighbor) {
+                    *neighbor_count -= 1;
+                    0
+                } else {
+                    1
+                }
+            })
+            .sum();
+        counts.insert(*p, count);
+    }
+    counts.values().sum()
+}
+
+fn part2(puzzle: &Puzzle) -> i32 {
+    // Find the bounding box for the puzzle points.
+    let bounds = Bounds3::from_points(puzzle.points.iter());
+    // Expand the bounds by 1 in each dimension so the BFS can go around the edges of the solid.
+    let bounds = bounds.outset(1);
+    let points: HashSet<_> = puzzle.points.iter().collect();
+
+    // Do a BFS from a point guaranteed to be outside the solid and find all reachable surfaces.
+    let mut count = 0;
+    search::bfs(
+        [bounds.min],
+        |p| {
+            let (solid, air): (Vec<_>, Vec<_>) = p
+                .bounded_neighbors(&bounds)
+                .partition(|neighbor| points.contains(neighbor));
+            count += solid.len() as i32;
+            air
+        },
+        |_| false,
+    );
+    count
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Puzzle = Puzzle;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<i32, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<i32, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "2,2,2\n", "1,2,2\n", "3,2,2\n", "2,1,2\n", "2,3,2\n", "2,2,1\n", "2,2,3\n", "2,2,4\n",
+        "2,2,6\n", "1,2,5\n", "3,2,5\n", "2,1,5\n", "2,3,5\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(64, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(58, part2(&parse(SAMPLE).unwrap()));
+    }
+}
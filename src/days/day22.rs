@@ -0,0 +1,453 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Direction4, Point2, Vector3};
+use crate::itertools::{IterTools, StrTools};
+use crate::solution::Solution;
+use crate::{oops, oops::Context, oops::Oops};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+enum Tile {
+    Wall,
+    Open,
+}
+
+enum Move {
+    Ahead(i32),
+    Left,
+    Right,
+}
+
+pub struct Puzzle {
+    map: HashMap<Point2, Tile>,
+    moves: Vec<Move>,
+    max_x: i32,
+    max_y: i32,
+}
+
+// Where a face of the net ends up once the net is folded into a cube: the face's outward normal,
+// and the directions that moving right (+x) and down (+y) on the map correspond to.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    normal: Vector3,
+    right: Vector3,
+    down: Vector3,
+}
+
+impl Face {
+    fn vector(&self, d: Direction4) -> Vector3 {
+        match d {
+            Direction4::North => -self.down,
+            Direction4::East => self.right,
+            Direction4::South => self.down,
+            Direction4::West => -self.right,
+        }
+    }
+
+    // Returns the face adjacent to this one in direction `d`. Folding along the shared edge turns
+    // `d` into the new face's normal, and this face's normal into the new face's `-d`.
+    fn fold(&self, d: Direction4) -> Face {
+        let mut face = *self;
+        face.normal = self.vector(d);
+        match d {
+            Direction4::North => face.down = self.normal,
+            Direction4::East => face.right = -self.normal,
+            Direction4::South => face.down = -self.normal,
+            Direction4::West => face.right = self.normal,
+        }
+        face
+    }
+}
+
+// The cube folded from the map's net. Faces are keyed by their position in the net, i.e. the
+// coordinates of their top-left tile divided by `size`.
+struct Cube {
+    size: i32,
+    faces: HashMap<Point2, Face>,
+}
+
+impl Cube {
+    fn fold(map: &HashMap<Point2, Tile>) -> Result<Cube, Oops> {
+        let size = (1..)
+            .find(|size| 6 * size * size >= map.len())
+            .filter(|size| 6 * size * size == map.len())
+            .ok_or_else(|| oops!("{} tiles cannot cover a cube", map.len()))?;
+        let size = i32::try_from(size)?;
+
+        // Walk the net from any face, folding across each edge shared with another face.
+        let start = map.keys().next().ok_or_else(|| oops!("empty map"))?;
+        let start = Point2::new(start.x / size, start.y / size);
+        let mut faces = HashMap::from([(
+            start,
+            Face {
+                normal: Vector3::new(0, 0, 1),
+                right: Vector3::new(1, 0, 0),
+                down: Vector3::new(0, 1, 0),
+            },
+        )]);
+        let mut pending = vec![start];
+        while let Some(p) = pending.pop() {
+            let face = faces[&p];
+            for d in Direction4::ALL {
+                let next = p + d.vector();
+                if map.contains_key(&Point2::new(next.x * size, next.y * size))
+                    && !faces.contains_key(&next)
+                {
+                    faces.insert(next, face.fold(d));
+                    pending.push(next);
+                }
+            }
+        }
+
+        let normals = faces.values().map(|f| f.normal).collect::<HashSet<_>>();
+        if faces.len() != 6 || normals.len() != 6 {
+            return Err(oops!("map is not the net of a cube"));
+        }
+        Ok(Cube { size, faces })
+    }
+
+    // Returns where moving off the edge of the map from `p` in direction `d` ends up, and the new
+    // direction. This maps `p` onto the surface of the cube, steps over the edge, then maps back.
+    fn wrap(&self, p: Point2, d: Direction4) -> (Point2, Direction4) {
+        let size = self.size;
+        let from = self.faces[&Point2::new(p.x / size, p.y / size)];
+        // Tile centers, in doubled coordinates so that they are integers, on a cube centered on
+        // the origin with edges of length `2 * size`.
+        let center = from.normal * size
+            + from.right * (2 * (p.x % size) + 1 - size)
+            + from.down * (2 * (p.y % size) + 1 - size);
+        let heading = from.vector(d);
+        let center = center + heading - from.normal;
+
+        // Folding guarantees every normal is present.
+        let (origin, to) = self
+            .faces
+            .iter()
+            .find(|(_, face)| face.normal == heading)
+            .unwrap();
+        let direction = Direction4::ALL
+            .into_iter()
+            .find(|d| to.vector(*d) == -from.normal)
+            .unwrap();
+        let p = Point2::new(
+            origin.x * size + (center.dot(to.right) + size - 1) / 2,
+            origin.y * size + (center.dot(to.down) + size - 1) / 2,
+        );
+        (p, direction)
+    }
+}
+
+impl Puzzle {
+    fn get_next2(&self, cube: &Cube, p: Point2, d: Direction4) -> Option<(Point2, Direction4)> {
+        let candidate = p + d.vector();
+        let (next, d) = if self.map.contains_key(&candidate) {
+            (candidate, d)
+        } else {
+            cube.wrap(p, d)
+        };
+        match self.map.get(&next) {
+            Some(Tile::Open) => Some((next, d)),
+            _ => None,
+        }
+    }
+
+    fn get_next(&self, p: Point2, d: Direction4) -> Option<Point2> {
+        let candidate = p + d.vector();
+
+        match self.map.get(&candidate) {
+            Some(Tile::Open) => return Some(candidate),
+            Some(Tile::Wall) => return None,
+            None => match d {
+                Direction4::North => {
+                    for y in (0..=self.max_y).rev() {
+                        match self.map.get(&Point2::new(p.x, y)) {
+                            Some(Tile::Open) => return Some(Point2::new(p.x, y)),
+                            Some(Tile::Wall) => return None,
+                            None => continue,
+                        }
+                    }
+                }
+                Direction4::East => {
+                    for x in 0.. {
+                        match self.map.get(&Point2::new(x, p.y)) {
+                            Some(Tile::Open) => return Some(Point2::new(x, p.y)),
+                            Some(Tile::Wall) => return None,
+                            None => continue,
+                        }
+                    }
+                }
+                Direction4::South => {
+                    for y in 0.. {
+                        match self.map.get(&Point2::new(p.x, y)) {
+                            Some(Tile::Open) => return Some(Point2::new(p.x, y)),
+                            Some(Tile::Wall) => return None,
+                            None => continue,
+                        }
+                    }
+                }
+                Direction4::West => {
+                    for x in (0..=self.max_x).rev() {
+                        match self.map.get(&Point2::new(x, p.y)) {
+                            Some(Tile::Open) => return Some(Point2::new(x, p.y)),
+                            Some(Tile::Wall) => return None,
+                            None => continue,
+                        }
+                    }
+                }
+            },
+        }
+        None
+    }
+}
+
+fn parse_moves(s: &str) -> Vec<Move> {
+    let mut moves = vec![];
+    let mut n = None;
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            n = match n {
+                Some(n) => Some(n * 10 + c as i32 - '0' as i32),
+                None => Some(c as i32 - '0' as i32),
+            };
+        } else {
+            if let Some(n) = n {
+                moves.push(Move::Ahead(n));
+            }
+            n = None;
+            match c {
+                'L' => {
+                    moves.push(Move::Left);
+                }
+                'R' => {
+                    moves.push(Move::Right);
+                }
+                _ => (),
+            }
+        }
+    }
+    // The move list may not end with a newline to flush the last distance.
+    if let Some(n) = n {
+        moves.push(Move::Ahead(n));
+    }
+    moves
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut map = HashMap::new();
+        let [map_str, moves_str] = s
+            .paragraphs()
+            .collect_array()
+            .context("expected a map and moves")?;
+        for (y, line) in map_str.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let p = Point2::new(x.try_into()?, y.try_into()?);
+                match c {
+                    '#' => {
+                        map.insert(p, Tile::Wall);
+                    }
+                    '.' => {
+                        map.insert(p, Tile::Open);
+                    }
+                    _ => (),
+                }
+            }
+        }
+        let max_x = map.keys().map(|p| p.x).max().unwrap();
+        let max_y = map.keys().map(|p| p.y).max().unwrap();
+        let moves = parse_moves(moves_str);
+        Ok(Puzzle {
+            map,
+            moves,
+            max_x,
+            max_y,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+#[allow(dead_code)]
+fn print(puzzle: &Puzzle, current_pos: Point2, d: Direction4) {
+    println!("Current state:");
+    for y in 0..puzzle.max_y {
+        println!(
+            "{}",
+            (0..puzzle.max_x)
+                .map(|x| {
+                    let p = Point2::new(x, y);
+                    if p == current_pos {
+                        return d.arrow();
+                    }
+                    if let Some(tile) = puzzle.map.get(&p) {
+                        match tile {
+                            Tile::Wall => '#',
+                            Tile::Open => '.',
+                        }
+                    } else {
+                        ' '
+                    }
+                })
+                .collect::<String>()
+        );
+    }
+}
+
+fn part1(puzzle: &Puzzle) -> i32 {
+    // Find the starting point.
+    let mut current_pos = Point2::new(0, 0);
+    for x in 0.. {
+        let p = Point2::new(x, 0);
+        if let Some(tile) = puzzle.map.get(&p) {
+            match tile {
+                Tile::Open => {
+                    current_pos = p;
+                    break;
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    let mut direction = Direction4::East;
+
+    for m in &puzzle.moves {
+        match m {
+            Move::Left => direction = direction.turn_left(),
+            Move::Right => direction = direction.turn_right(),
+            Move::Ahead(n) => {
+                for _ in 0..*n {
+                    if let Some(next) = puzzle.get_next(current_pos, direction) {
+                        current_pos = next;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    1000 * (current_pos.y + 1) + 4 * (current_pos.x + 1) + direction.facing()
+}
+
+fn part2(puzzle: &Puzzle) -> Result<i32, Oops> {
+    let cube = Cube::fold(&puzzle.map)?;
+
+    // Find the starting point.
+    let mut current_pos = Point2::new(0, 0);
+    for x in 0.. {
+        let p = Point2::new(x, 0);
+        if let Some(tile) = puzzle.map.get(&p) {
+            match tile {
+                Tile::Open => {
+                    current_pos = p;
+                    break;
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    let mut direction = Direction4::East;
+
+    for m in &puzzle.moves {
+        match m {
+            Move::Left => direction = direction.turn_left(),
+            Move::Right => direction = direction.turn_right(),
+            Move::Ahead(n) => {
+                for _ in 0..*n {
+                    if let Some(next) = puzzle.get_next2(&cube, current_pos, direction) {
+                        current_pos = next.0;
+                        direction = next.1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Ok(1000 * (current_pos.y + 1) + 4 * (current_pos.x + 1) + direction.facing())
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Puzzle = Puzzle;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<i32, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<i32, Oops> {
+        part2(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "        ...#\n",
+        "        .#..\n",
+        "        #...\n",
+        "        ....\n",
+        "...#.......#\n",
+        "........#...\n",
+        "..#....#....\n",
+        "..........#.\n",
+        "        ...#....\n",
+        "        .....#..\n",
+        "        .#......\n",
+        "        ......#.\n",
+        "\n",
+        "10R5L5R10L4R5L5\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(6032, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(5031, part2(&parse(SAMPLE).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn wrap_round_trip() {
+        let puzzle = parse(SAMPLE).unwrap();
+        let cube = Cube::fold(&puzzle.map).unwrap();
+        for p in puzzle.map.keys() {
+            for d in Direction4::ALL {
+                if puzzle.map.contains_key(&(*p + d.vector())) {
+                    continue;
+                }
+                let (q, e) = cube.wrap(*p, d);
+                assert!(puzzle.map.contains_key(&q));
+                assert_eq!((*p, d.turn_around()), cube.wrap(q, e.turn_around()));
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{BitGrid, Bounds2, Direction4, Direction8, NeighborMask, Point2, SparseGrid};
+use crate::solution::Solution;
+use crate::{oops, oops::Oops};
+use std::str::FromStr;
+
+pub struct Puzzle {
+    elves: Vec<Point2>,
+}
+
+// The elves and their occupancy, updated in place each round.
+struct Simulation {
+    elves: Vec<Point2>,
+    occupied: BitGrid,
+    // Scratch space for `step`, kept to reuse its storage: each elf's proposed position, the cells
+    // proposed by at least one elf, and the cells proposed by more than one.
+    proposals: Vec<Point2>,
+    proposed: BitGrid,
+    contested: BitGrid,
+}
+
+impl Simulation {
+    fn new(elves: &[Point2]) -> Self {
+        Simulation {
+            elves: elves.to_vec(),
+            occupied: elves.iter().copied().collect(),
+            proposals: Vec::with_capacity(elves.len()),
+            proposed: BitGrid::new(),
+            contested: BitGrid::new(),
+        }
+    }
+
+    // Runs one round, returning true if any elf moved.
+    fn step(&mut self, round: usize) -> bool {
+        self.proposals.clear();
+        self.proposed.clear();
+        self.contested.clear();
+        for elf in &self.elves {
+            let proposal = find_next_position(*elf, &self.occupied, round);
+            if proposal != *elf && !self.proposed.insert(proposal) {
+                self.contested.insert(proposal);
+            }
+            self.proposals.push(proposal);
+        }
+
+        let mut moved = false;
+        for (elf, proposal) in self.elves.iter_mut().zip(&self.proposals) {
+            if *proposal != *elf && !self.contested.contains(*proposal) {
+                self.occupied.remove(*elf);
+                self.occupied.insert(*proposal);
+                *elf = *proposal;
+                moved = true;
+            }
+        }
+        moved
+    }
+}
+
+fn find_next_position(current: Point2, occupied: &BitGrid, round: usize) -> Point2 {
+    const DIRECTIONS: [Direction4; 4] = [
+        Direction4::North,
+        Direction4::South,
+        Direction4::West,
+        Direction4::East,
+    ];
+
+    let neighbors = occupied.neighbor_mask(current);
+    if neighbors.is_empty() {
+        return current;
+    }
+
+    for i in 0..DIRECTIONS.len() {
+        let direction = DIRECTIONS[(round + i) % 4];
+        // An elf only moves in a direction if that side, including both diagonals, is empty.
+        let d = Direction8::from(direction);
+        let to_check = NeighborMask::from(d)
+            | NeighborMask::from(d.turn_left_45())
+            | NeighborMask::from(d.turn_right_45());
+        if neighbors.intersects(to_check) {
+            continue;
+        }
+        return current + direction.vector();
+    }
+
+    current
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elves = s
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(move |(x, c)| match c {
+                        '#' => Ok(Some(Point2::new(x.try_into()?, y.try_into()?))),
+                        '.' => Ok(None),
+                        _ => Err(oops!("bad tile")),
+                    })
+                    .filter_map(|x| match x {
+                        Ok(None) => None,
+                        Ok(Some(x)) => Some(Ok(x)),
+                        Err(e) => Some(Err(e)),
+                    })
+            })
+            .collect::<Result<Vec<_>, Oops>>()?;
+
+        Ok(Puzzle { elves })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+#[allow(dead_code)]
+fn print(positions: &[Point2]) {
+    let grid: SparseGrid<()> = positions.iter().map(|p| (*p, ())).collect();
+    println!("Positions: ");
+    println!("{}", grid.render(|()| '#', '.'));
+}
+
+fn part1(puzzle: &Puzzle) -> usize {
+    let mut simulation = Simulation::new(&puzzle.elves);
+    for round in 0..10 {
+        simulation.step(round);
+    }
+    let elves = &simulation.elves;
+    Bounds2::from_points(elves.iter()).area() as usize - elves.len()
+}
+
+fn part2(puzzle: &Puzzle) -> usize {
+    let mut simulation = Simulation::new(&puzzle.elves);
+    for round in 0.. {
+        if !simulation.step(round) {
+            return round + 1;
+        }
+    }
+    0
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Puzzle = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "..............\n",
+        "..............\n",
+        ".......#......\n",
+        ".....###.#....\n",
+        "...#...#.#....\n",
+        "....#...##....\n",
+        "...#.###......\n",
+        "...##.#.##....\n",
+        "....#..#......\n",
+        "..............\n",
+        "..............\n",
+        "..............\n",
+    );
+
+    #[test]
+    fn example1() {
+        assert_eq!(110, part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(20, part2(&parse(SAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,192 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::solution::Solution;
+use crate::{oops, oops::Oops};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub struct Puzzle {
+    values: Vec<Snafu>,
+}
+
+enum SnafuDigit {
+    DoubleMinus,
+    Minus,
+    Zero,
+    One,
+    Two,
+}
+
+impl SnafuDigit {
+    fn to_i64(&self) -> i64 {
+        match self {
+            SnafuDigit::DoubleMinus => -2,
+            SnafuDigit::Minus => -1,
+            SnafuDigit::Zero => 0,
+            SnafuDigit::One => 1,
+            SnafuDigit::Two => 2,
+        }
+    }
+}
+
+struct Snafu {
+    // Digits stored from least-significant to most-significant digit.
+    digits: Vec<SnafuDigit>,
+}
+
+impl Snafu {
+    fn to_i64(&self) -> i64 {
+        self.digits
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (position, digit)| {
+                acc + 5i64.pow(position as u32) * digit.to_i64()
+            })
+    }
+
+    fn from_i64(mut x: i64) -> Self {
+        let mut digits = vec![];
+        while x != 0 {
+            let current = x % 5;
+            let digit = match current {
+                0 => SnafuDigit::Zero,
+                1 => SnafuDigit::One,
+                2 => SnafuDigit::Two,
+                3 => SnafuDigit::DoubleMinus,
+                4 => SnafuDigit::Minus,
+                _ => panic!(),
+            };
+            x = (x - digit.to_i64()) / 5;
+            digits.push(digit);
+        }
+        Self { digits }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .char_indices()
+            .rev()
+            .map(|(i, c)| {
+                Ok::<_, Oops>(match c {
+                    '=' => SnafuDigit::DoubleMinus,
+                    '-' => SnafuDigit::Minus,
+                    '0' => SnafuDigit::Zero,
+                    '1' => SnafuDigit::One,
+                    '2' => SnafuDigit::Two,
+                    _ => Err(oops!("bad digit {c}").at_column(i + 1).with_text(s))?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Snafu { digits })
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for digit in self.digits.iter().rev() {
+            write!(
+                f,
+                "{}",
+                match digit {
+                    SnafuDigit::DoubleMinus => '=',
+                    SnafuDigit::Minus => '-',
+                    SnafuDigit::Zero => '0',
+                    SnafuDigit::One => '1',
+                    SnafuDigit::Two => '2',
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {
+            values: oops::parse_lines(s)?,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Puzzle, Oops> {
+    input.parse()
+}
+
+fn part1(puzzle: &Puzzle) -> String {
+    Snafu::from_i64(puzzle.values.iter().map(|x| x.to_i64()).sum()).to_string()
+}
+
+fn part2(_puzzle: &Puzzle) -> usize {
+    0
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Puzzle = Puzzle;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, Oops> {
+        parse(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<String, Oops> {
+        Ok(part1(puzzle))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<usize, Oops> {
+        Ok(part2(puzzle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = concat!(
+        "1=-0-2\n", "12111\n", "2=0=\n", "21\n", "2=01\n", "111\n", "20012\n", "112\n", "1=-1=\n",
+        "1-12\n", "12\n", "1=\n", "122\n",
+    );
+
+    #[test]
+    fn snafu_to_i64() {
+        assert_eq!(
+            4890i64,
+            parse(SAMPLE)
+                .unwrap()
+                .values
+                .iter()
+                .map(Snafu::to_i64)
+                .sum()
+        );
+    }
+
+    #[test]
+    fn example1() {
+        assert_eq!("2=-1=0", part1(&parse(SAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(0, part2(&parse(SAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,78 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+pub mod day00;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use crate::solution::{self, Solver};
+use std::ops::RangeInclusive;
+
+// Day 0 is a template for new days and is intentionally not listed here.
+pub const ALL: RangeInclusive<u32> = 1..=25;
+
+#[must_use]
+pub fn solver(day: u32) -> Option<Solver> {
+    Some(match day {
+        1 => solution::measure::<day01::Day01>,
+        2 => solution::measure::<day02::Day02>,
+        3 => solution::measure::<day03::Day03>,
+        4 => solution::measure::<day04::Day04>,
+        5 => solution::measure::<day05::Day05>,
+        6 => solution::measure::<day06::Day06>,
+        7 => solution::measure::<day07::Day07>,
+        8 => solution::measure::<day08::Day08>,
+        9 => solution::measure::<day09::Day09>,
+        10 => solution::measure::<day10::Day10>,
+        11 => solution::measure::<day11::Day11>,
+        12 => solution::measure::<day12::Day12>,
+        13 => solution::measure::<day13::Day13>,
+        14 => solution::measure::<day14::Day14>,
+        15 => solution::measure::<day15::Day15>,
+        16 => solution::measure::<day16::Day16>,
+        17 => solution::measure::<day17::Day17>,
+        18 => solution::measure::<day18::Day18>,
+        19 => solution::measure::<day19::Day19>,
+        20 => solution::measure::<day20::Day20>,
+        21 => solution::measure::<day21::Day21>,
+        22 => solution::measure::<day22::Day22>,
+        23 => solution::measure::<day23::Day23>,
+        24 => solution::measure::<day24::Day24>,
+        25 => solution::measure::<day25::Day25>,
+        _ => return None,
+    })
+}
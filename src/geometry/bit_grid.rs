@@ -0,0 +1,512 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Bounds2, Point2};
+use std::ops::BitOr;
+
+// The occupied neighbors of a cell, one bit per compass direction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NeighborMask(pub u8);
+
+impl NeighborMask {
+    pub const NONE: Self = Self(0);
+    pub const N: Self = Self(1 << 0);
+    pub const NE: Self = Self(1 << 1);
+    pub const E: Self = Self(1 << 2);
+    pub const SE: Self = Self(1 << 3);
+    pub const S: Self = Self(1 << 4);
+    pub const SW: Self = Self(1 << 5);
+    pub const W: Self = Self(1 << 6);
+    pub const NW: Self = Self(1 << 7);
+
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[must_use]
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for NeighborMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+// A set of points packed into one bit per cell, for occupancy simulations where a `HashSet<Point2>`
+// would be too slow. Rows are stored as runs of `u64` words and storage grows as needed, so any
+// point may be inserted. As with `Grid`, y increases downwards, so north is y - 1.
+#[derive(Clone, Debug)]
+pub struct BitGrid {
+    bits: Vec<u64>,
+    // The point stored in bit 0 of the first word.
+    origin: Point2,
+    words_per_row: usize,
+    height: usize,
+}
+
+impl BitGrid {
+    #[must_use]
+    pub fn new() -> Self {
+        BitGrid {
+            bits: vec![],
+            origin: Point2::new(0, 0),
+            words_per_row: 0,
+            height: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        self.row_window(p.y, p.x) & 1 != 0
+    }
+
+    // Returns true if `p` was not already set.
+    pub fn insert(&mut self, p: Point2) -> bool {
+        self.reserve(p);
+        let (i, bit) = self.locate(p).expect("storage was just reserved");
+        let was_set = self.bits[i] & bit != 0;
+        self.bits[i] |= bit;
+        !was_set
+    }
+
+    // Returns true if `p` was set.
+    pub fn remove(&mut self, p: Point2) -> bool {
+        let Some((i, bit)) = self.locate(p) else {
+            return false;
+        };
+        let was_set = self.bits[i] & bit != 0;
+        self.bits[i] &= !bit;
+        was_set
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|w| *w == 0)
+    }
+
+    // The smallest bounds containing every set point, or `None` if nothing is set.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds2> {
+        let mut points = self.iter();
+        let first = points.next()?;
+        Some(points.fold(
+            Bounds2 {
+                min: first,
+                max: first,
+            },
+            |b, p| Bounds2 {
+                min: Point2::new(std::cmp::min(b.min.x, p.x), b.min.y),
+                max: Point2::new(std::cmp::max(b.max.x, p.x), p.y),
+            },
+        ))
+    }
+
+    // Iterates over the set points in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.bits.iter().enumerate().flat_map(move |(i, &word)| {
+            let row = i / self.words_per_row;
+            let x = self.origin.x + (i % self.words_per_row * 64) as i32;
+            let y = self.origin.y + row as i32;
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros();
+                word &= word - 1;
+                Some(Point2::new(x + bit as i32, y))
+            })
+        })
+    }
+
+    // The 64 cells of row `y` starting at column `x`: bit `i` of the result is set if (x + i, y)
+    // is. This works for any alignment, and cells outside the storage read as unset.
+    #[must_use]
+    pub fn row_window(&self, y: i32, x: i32) -> u64 {
+        let row = y - self.origin.y;
+        if row < 0 || row as usize >= self.height {
+            return 0;
+        }
+        let col = x - self.origin.x;
+        let (word, bit) = (col.div_euclid(64), col.rem_euclid(64));
+        let low = self.word(row as usize, word) >> bit;
+        if bit == 0 {
+            low
+        } else {
+            low | (self.word(row as usize, word + 1) << (64 - bit))
+        }
+    }
+
+    // The write counterpart of `row_window`: sets cell (x + i, y) for every bit `i` set in `mask`,
+    // growing the storage if needed.
+    pub fn or_row_window(&mut self, y: i32, x: i32, mask: u64) {
+        if mask == 0 {
+            return;
+        }
+        self.reserve(Point2::new(x + mask.trailing_zeros() as i32, y));
+        self.reserve(Point2::new(x + 63 - mask.leading_zeros() as i32, y));
+        let row = (y - self.origin.y) as usize;
+        let col = x - self.origin.x;
+        let (word, bit) = (col.div_euclid(64), col.rem_euclid(64));
+        let parts = if bit == 0 {
+            [mask, 0]
+        } else {
+            [mask << bit, mask >> (64 - bit)]
+        };
+        // Only words holding set bits are touched, and those were just reserved.
+        for (word, part) in (word..).zip(parts) {
+            if part != 0 {
+                self.bits[row * self.words_per_row + word as usize] |= part;
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn neighbor_mask(&self, p: Point2) -> NeighborMask {
+        // Bit 0 is x - 1, bit 1 is x and bit 2 is x + 1.
+        let above = self.row_window(p.y - 1, p.x - 1);
+        let middle = self.row_window(p.y, p.x - 1);
+        let below = self.row_window(p.y + 1, p.x - 1);
+        let bit = |row: u64, i: u32, mask: NeighborMask| {
+            if row >> i & 1 != 0 {
+                mask
+            } else {
+                NeighborMask::NONE
+            }
+        };
+        bit(above, 0, NeighborMask::NW)
+            | bit(above, 1, NeighborMask::N)
+            | bit(above, 2, NeighborMask::NE)
+            | bit(middle, 2, NeighborMask::E)
+            | bit(below, 2, NeighborMask::SE)
+            | bit(below, 1, NeighborMask::S)
+            | bit(below, 0, NeighborMask::SW)
+            | bit(middle, 0, NeighborMask::W)
+    }
+
+    // Moves every set cell in row `y` by `dx` columns, growing the storage if needed.
+    pub fn shift_row(&mut self, y: i32, dx: i32) {
+        if dx == 0 {
+            return;
+        }
+        let Some((first, last)) = self.row_extent(y) else {
+            return;
+        };
+        self.reserve(Point2::new(first + dx, y));
+        self.reserve(Point2::new(last + dx, y));
+
+        let row = (y - self.origin.y) as usize;
+        let start = row * self.words_per_row;
+        let old = self.bits[start..start + self.words_per_row].to_vec();
+        let get = |i: isize| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| old.get(i))
+                .copied()
+                .unwrap_or(0)
+        };
+        let (words, bits) = (dx.unsigned_abs() as isize / 64, dx.unsigned_abs() % 64);
+        for i in 0..self.words_per_row {
+            let i_signed = i as isize;
+            self.bits[start + i] = if dx > 0 {
+                let low = get(i_signed - words - 1);
+                let high = get(i_signed - words);
+                if bits == 0 {
+                    high
+                } else {
+                    (high << bits) | (low >> (64 - bits))
+                }
+            } else {
+                let low = get(i_signed + words);
+                let high = get(i_signed + words + 1);
+                if bits == 0 {
+                    low
+                } else {
+                    (low >> bits) | (high << (64 - bits))
+                }
+            };
+        }
+    }
+
+    // Draws the cells within `bounds()`, one line per row with no trailing newline.
+    #[must_use]
+    pub fn render(&self, set: char, unset: char) -> String {
+        let Some(bounds) = self.bounds() else {
+            return String::new();
+        };
+        (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| {
+                        if self.contains(Point2::new(x, y)) {
+                            set
+                        } else {
+                            unset
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // The columns of the first and last set cells in row `y`, if any are set.
+    fn row_extent(&self, y: i32) -> Option<(i32, i32)> {
+        let row = usize::try_from(y - self.origin.y)
+            .ok()
+            .filter(|row| *row < self.height)?;
+        let words = &self.bits[row * self.words_per_row..(row + 1) * self.words_per_row];
+        let first = words.iter().position(|w| *w != 0)?;
+        let last = words.iter().rposition(|w| *w != 0)?;
+        Some((
+            self.origin.x + (first * 64) as i32 + words[first].trailing_zeros() as i32,
+            self.origin.x + (last * 64 + 63) as i32 - words[last].leading_zeros() as i32,
+        ))
+    }
+
+    fn word(&self, row: usize, word: i32) -> u64 {
+        if word < 0 || word as usize >= self.words_per_row {
+            0
+        } else {
+            self.bits[row * self.words_per_row + word as usize]
+        }
+    }
+
+    // Returns the index of the word holding `p` and the mask for its bit, if `p` is in storage.
+    fn locate(&self, p: Point2) -> Option<(usize, u64)> {
+        let row = usize::try_from(p.y - self.origin.y).ok()?;
+        let col = usize::try_from(p.x - self.origin.x).ok()?;
+        if row >= self.height || col >= self.words_per_row * 64 {
+            return None;
+        }
+        Some((row * self.words_per_row + col / 64, 1 << (col % 64)))
+    }
+
+    // Grows the storage so that it includes `p`. Growth is at least geometric, so that repeatedly
+    // inserting just outside the edge stays cheap.
+    fn reserve(&mut self, p: Point2) {
+        if self.height == 0 {
+            *self = BitGrid {
+                bits: vec![0],
+                origin: Point2::new(p.x - 32, p.y),
+                words_per_row: 1,
+                height: 1,
+            };
+            return;
+        }
+        let col = i64::from(p.x - self.origin.x);
+        let row = i64::from(p.y - self.origin.y);
+        let (words, height) = (self.words_per_row as i64, self.height as i64);
+        let left = if col < 0 {
+            std::cmp::max((-col + 63) / 64, words)
+        } else {
+            0
+        };
+        let right = if col >= words * 64 {
+            std::cmp::max((col - words * 64) / 64 + 1, words)
+        } else {
+            0
+        };
+        let top = if row < 0 {
+            std::cmp::max(-row, height)
+        } else {
+            0
+        };
+        let bottom = if row >= height {
+            std::cmp::max(row - height + 1, height)
+        } else {
+            0
+        };
+        if left + right + top + bottom == 0 {
+            return;
+        }
+
+        let new_words = (left + words + right) as usize;
+        let new_height = (top + height + bottom) as usize;
+        let mut bits = vec![0; new_words * new_height];
+        for (r, old_row) in self.bits.chunks_exact(self.words_per_row).enumerate() {
+            let start = (r + top as usize) * new_words + left as usize;
+            bits[start..start + self.words_per_row].copy_from_slice(old_row);
+        }
+        *self = BitGrid {
+            bits,
+            origin: Point2::new(self.origin.x - 64 * left as i32, self.origin.y - top as i32),
+            words_per_row: new_words,
+            height: new_height,
+        };
+    }
+}
+
+impl Default for BitGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<Point2> for BitGrid {
+    fn from_iter<I: IntoIterator<Item = Point2>>(iter: I) -> Self {
+        let mut grid = BitGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl Extend<Point2> for BitGrid {
+    fn extend<I: IntoIterator<Item = Point2>>(&mut self, iter: I) {
+        for p in iter {
+            self.insert(p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i32, y: i32) -> Point2 {
+        Point2::new(x, y)
+    }
+
+    #[test]
+    fn insert_and_grow() {
+        let mut grid = BitGrid::new();
+        assert!(grid.is_empty());
+        assert!(!grid.contains(p(0, 0)));
+        // Grow in every direction, across several words.
+        let points = [p(0, 0), p(-200, 3), p(500, -40), p(63, 64), p(64, 64)];
+        for q in points {
+            assert!(grid.insert(q));
+        }
+        assert!(!grid.insert(p(0, 0)));
+        for q in points {
+            assert!(grid.contains(q), "{q:?}");
+        }
+        assert!(!grid.contains(p(1, 0)));
+        assert_eq!(points.len(), grid.len());
+
+        assert!(grid.remove(p(63, 64)));
+        assert!(!grid.remove(p(63, 64)));
+        assert!(!grid.remove(p(10_000, 0)));
+        assert_eq!(points.len() - 1, grid.len());
+    }
+
+    #[test]
+    fn bounds_and_iteration() {
+        let grid: BitGrid = [p(70, 2), p(-5, 1), p(3, 2), p(0, -1)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            vec![p(0, -1), p(-5, 1), p(3, 2), p(70, 2)],
+            grid.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(Bounds2 {
+                min: p(-5, -1),
+                max: p(70, 2)
+            }),
+            grid.bounds()
+        );
+        assert_eq!(None, BitGrid::new().bounds());
+    }
+
+    #[test]
+    fn row_window() {
+        let grid: BitGrid = [p(-1, 0), p(0, 0), p(62, 0), p(63, 0), p(64, 0)]
+            .into_iter()
+            .collect();
+        assert_eq!(1 << 63 | 0b11, grid.row_window(0, -1));
+        assert_eq!(0b111, grid.row_window(0, 62));
+        assert_eq!(0b1110, grid.row_window(0, 61));
+        assert_eq!(0b111 << 61, grid.row_window(0, 1));
+        assert_eq!(0, grid.row_window(1, 0));
+    }
+
+    #[test]
+    fn or_row_window() {
+        let mut grid = BitGrid::new();
+        grid.or_row_window(0, 0, 0);
+        assert!(grid.is_empty());
+        grid.or_row_window(0, -1, 1 << 63 | 0b11);
+        assert_eq!(
+            vec![p(-1, 0), p(0, 0), p(62, 0)],
+            grid.iter().collect::<Vec<_>>()
+        );
+        // Grows the storage both ways.
+        grid.or_row_window(5, 100, 0b101 << 20);
+        grid.or_row_window(5, -300, 1);
+        assert_eq!(0b101, grid.row_window(5, 120));
+        assert!(grid.contains(p(-300, 5)));
+        assert_eq!(6, grid.len());
+        // Bits that are already set stay set.
+        grid.or_row_window(0, 0, 0b110);
+        assert_eq!(0b111, grid.row_window(0, 0) & 0b1111);
+    }
+
+    #[test]
+    fn neighbor_mask() {
+        let grid: BitGrid = [p(0, -1), p(1, -1), p(-1, 0), p(1, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            NeighborMask::N | NeighborMask::NE | NeighborMask::W | NeighborMask::SE,
+            grid.neighbor_mask(p(0, 0))
+        );
+        assert!(grid.neighbor_mask(p(10, 10)).is_empty());
+        let north = NeighborMask::N | NeighborMask::NE | NeighborMask::NW;
+        assert!(grid.neighbor_mask(p(0, 0)).intersects(north));
+        assert!(!grid.neighbor_mask(p(-1, -1)).intersects(north));
+    }
+
+    #[test]
+    fn shift_row() {
+        let mut grid: BitGrid = [p(0, 0), p(5, 0), p(1, 1)].into_iter().collect();
+        grid.shift_row(0, 100);
+        assert_eq!(
+            vec![p(100, 0), p(105, 0), p(1, 1)],
+            grid.iter().collect::<Vec<_>>()
+        );
+        grid.shift_row(0, -203);
+        assert_eq!(
+            vec![p(-103, 0), p(-98, 0), p(1, 1)],
+            grid.iter().collect::<Vec<_>>()
+        );
+        grid.shift_row(1, -1);
+        grid.shift_row(7, 3);
+        assert_eq!(
+            vec![p(-103, 0), p(-98, 0), p(0, 1)],
+            grid.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn render() {
+        let grid: BitGrid = [p(2, 2), p(4, 3)].into_iter().collect();
+        assert_eq!("#..\n..#", grid.render('#', '.'));
+    }
+}
@@ -0,0 +1,481 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Coord, Point2, Point3};
+use std::borrow::Borrow;
+use std::fmt::Display;
+
+// An axis-aligned rectangle of points, inclusive of both `min` and `max`. Bounds where `min` is
+// greater than `max` along any axis contain no points; `from_points()` returns such bounds when
+// given no points.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bounds2<T = i32> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> Bounds2<T> {
+    #[must_use]
+    pub const fn new(min: Point2<T>, max: Point2<T>) -> Self {
+        Bounds2 { min, max }
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: &Point2<T>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+
+    #[must_use]
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    #[must_use]
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    // The number of points contained in these bounds.
+    #[must_use]
+    pub fn area(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.width() * self.height()
+        }
+    }
+
+    // The points contained in both bounds, or `None` if they do not overlap.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let b = Bounds2 {
+            min: Point2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: Point2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        };
+        (!b.is_empty()).then_some(b)
+    }
+
+    // The smallest bounds containing both bounds.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Bounds2 {
+            min: Point2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    // Grows the bounds by `n` in every direction.
+    #[must_use]
+    pub fn outset(&self, n: T) -> Self {
+        Bounds2 {
+            min: Point2::new(self.min.x - n, self.min.y - n),
+            max: Point2::new(self.max.x + n, self.max.y + n),
+        }
+    }
+
+    // Shrinks the bounds by `n` in every direction.
+    #[must_use]
+    pub fn inset(&self, n: T) -> Self {
+        self.outset(-n)
+    }
+
+    // The nearest point in bounds to `p`. The bounds must not be empty.
+    #[must_use]
+    pub fn clamp(&self, p: Point2<T>) -> Point2<T> {
+        Point2::new(
+            p.x.clamp(self.min.x, self.max.x),
+            p.y.clamp(self.min.y, self.max.y),
+        )
+    }
+
+    // Wraps `p` around the edges of the bounds, as if they were a torus. The bounds must not be
+    // empty.
+    #[must_use]
+    pub fn wrap(&self, p: Point2<T>) -> Point2<T> {
+        Point2::new(
+            self.min.x + (p.x - self.min.x).rem_euclid(self.width()),
+            self.min.y + (p.y - self.min.y).rem_euclid(self.height()),
+        )
+    }
+
+    // Iterates over every point in bounds in row-major order.
+    #[must_use]
+    pub fn points(&self) -> Points2<T> {
+        Points2 {
+            bounds: *self,
+            next: (!self.is_empty()).then_some(self.min),
+        }
+    }
+
+    #[must_use]
+    pub fn from_points<I>(i: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Point2<T>>,
+    {
+        i.into_iter().fold(Self::new_uninitialized(), |b, p| Self {
+            min: Point2::new(
+                std::cmp::min(b.min.x, p.borrow().x),
+                std::cmp::min(b.min.y, p.borrow().y),
+            ),
+            max: Point2::new(
+                std::cmp::max(b.max.x, p.borrow().x),
+                std::cmp::max(b.max.y, p.borrow().y),
+            ),
+        })
+    }
+
+    #[must_use]
+    fn new_uninitialized() -> Self {
+        Bounds2 {
+            min: Point2::new(T::MAX, T::MAX),
+            max: Point2::new(T::MIN, T::MIN),
+        }
+    }
+}
+
+impl<T: Coord> Display for Bounds2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {})..=({}, {})",
+            self.min.x, self.min.y, self.max.x, self.max.y
+        )
+    }
+}
+
+pub struct Points2<T = i32> {
+    bounds: Bounds2<T>,
+    next: Option<Point2<T>>,
+}
+
+impl<T: Coord> Iterator for Points2<T> {
+    type Item = Point2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.next?;
+        self.next = if p.x < self.bounds.max.x {
+            Some(Point2::new(p.x + T::ONE, p.y))
+        } else if p.y < self.bounds.max.y {
+            Some(Point2::new(self.bounds.min.x, p.y + T::ONE))
+        } else {
+            None
+        };
+        Some(p)
+    }
+}
+
+// The 3D equivalent of `Bounds2`: an axis-aligned cuboid, inclusive of both `min` and `max`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bounds3<T = i32> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coord> Bounds3<T> {
+    #[must_use]
+    pub const fn new(min: Point3<T>, max: Point3<T>) -> Self {
+        Bounds3 { min, max }
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: &Point3<T>) -> bool {
+        p.x >= self.min.x
+            && p.x <= self.max.x
+            && p.y >= self.min.y
+            && p.y <= self.max.y
+            && p.z >= self.min.z
+            && p.z <= self.max.z
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    #[must_use]
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    #[must_use]
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    #[must_use]
+    pub fn depth(&self) -> T {
+        self.max.z - self.min.z + T::ONE
+    }
+
+    // The number of points contained in these bounds.
+    #[must_use]
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.width() * self.height() * self.depth()
+        }
+    }
+
+    // The points contained in both bounds, or `None` if they do not overlap.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let b = Bounds3 {
+            min: Point3::new(
+                self.min.x.max(other.min.x),
+                self.min.y.max(other.min.y),
+                self.min.z.max(other.min.z),
+            ),
+            max: Point3::new(
+                self.max.x.min(other.max.x),
+                self.max.y.min(other.max.y),
+                self.max.z.min(other.max.z),
+            ),
+        };
+        (!b.is_empty()).then_some(b)
+    }
+
+    // The smallest bounds containing both bounds.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Bounds3 {
+            min: Point3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Point3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    // Grows the bounds by `n` in every direction.
+    #[must_use]
+    pub fn outset(&self, n: T) -> Self {
+        Bounds3 {
+            min: Point3::new(self.min.x - n, self.min.y - n, self.min.z - n),
+            max: Point3::new(self.max.x + n, self.max.y + n, self.max.z + n),
+        }
+    }
+
+    // Shrinks the bounds by `n` in every direction.
+    #[must_use]
+    pub fn inset(&self, n: T) -> Self {
+        self.outset(-n)
+    }
+
+    // The nearest point in bounds to `p`. The bounds must not be empty.
+    #[must_use]
+    pub fn clamp(&self, p: Point3<T>) -> Point3<T> {
+        Point3::new(
+            p.x.clamp(self.min.x, self.max.x),
+            p.y.clamp(self.min.y, self.max.y),
+            p.z.clamp(self.min.z, self.max.z),
+        )
+    }
+
+    // Wraps `p` around the edges of the bounds. The bounds must not be empty.
+    #[must_use]
+    pub fn wrap(&self, p: Point3<T>) -> Point3<T> {
+        Point3::new(
+            self.min.x + (p.x - self.min.x).rem_euclid(self.width()),
+            self.min.y + (p.y - self.min.y).rem_euclid(self.height()),
+            self.min.z + (p.z - self.min.z).rem_euclid(self.depth()),
+        )
+    }
+
+    // Iterates over every point in bounds, ordered by z, then y, then x.
+    #[must_use]
+    pub fn points(&self) -> Points3<T> {
+        Points3 {
+            bounds: *self,
+            next: (!self.is_empty()).then_some(self.min),
+        }
+    }
+
+    #[must_use]
+    pub fn from_points<I>(i: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Point3<T>>,
+    {
+        i.into_iter().fold(Self::new_uninitialized(), |b, p| Self {
+            min: Point3::new(
+                std::cmp::min(b.min.x, p.borrow().x),
+                std::cmp::min(b.min.y, p.borrow().y),
+                std::cmp::min(b.min.z, p.borrow().z),
+            ),
+            max: Point3::new(
+                std::cmp::max(b.max.x, p.borrow().x),
+                std::cmp::max(b.max.y, p.borrow().y),
+                std::cmp::max(b.max.z, p.borrow().z),
+            ),
+        })
+    }
+
+    #[must_use]
+    fn new_uninitialized() -> Self {
+        Self {
+            min: Point3::new(T::MAX, T::MAX, T::MAX),
+            max: Point3::new(T::MIN, T::MIN, T::MIN),
+        }
+    }
+}
+
+impl<T: Coord> Display for Bounds3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {}, {})..=({}, {}, {})",
+            self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z
+        )
+    }
+}
+
+pub struct Points3<T = i32> {
+    bounds: Bounds3<T>,
+    next: Option<Point3<T>>,
+}
+
+impl<T: Coord> Iterator for Points3<T> {
+    type Item = Point3<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.next?;
+        let b = &self.bounds;
+        self.next = if p.x < b.max.x {
+            Some(Point3::new(p.x + T::ONE, p.y, p.z))
+        } else if p.y < b.max.y {
+            Some(Point3::new(b.min.x, p.y + T::ONE, p.z))
+        } else if p.z < b.max.z {
+            Some(Point3::new(b.min.x, b.min.y, p.z + T::ONE))
+        } else {
+            None
+        };
+        Some(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x1: i32, y1: i32, x2: i32, y2: i32) -> Bounds2 {
+        Bounds2::new(Point2::new(x1, y1), Point2::new(x2, y2))
+    }
+
+    #[test]
+    fn area_and_emptiness() {
+        assert_eq!(12, rect(0, 0, 3, 2).area());
+        assert_eq!(1, rect(5, 5, 5, 5).area());
+        assert!(rect(1, 0, 0, 0).is_empty());
+        assert_eq!(0, rect(1, 0, 0, 0).area());
+        assert!(Bounds2::<i32>::from_points(std::iter::empty::<Point2>()).is_empty());
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = rect(0, 0, 4, 4);
+        let b = rect(2, 3, 6, 8);
+        assert_eq!(Some(rect(2, 3, 4, 4)), a.intersection(&b));
+        assert_eq!(None, a.intersection(&rect(5, 0, 6, 1)));
+        assert_eq!(rect(0, 0, 6, 8), a.union(&b));
+        assert_eq!(a, a.union(&rect(1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn outset_and_inset() {
+        let b = rect(0, 0, 4, 4);
+        assert_eq!(rect(-1, -1, 5, 5), b.outset(1));
+        assert_eq!(rect(1, 1, 3, 3), b.inset(1));
+        assert!(b.inset(3).is_empty());
+    }
+
+    #[test]
+    fn clamp_and_wrap() {
+        let b = rect(1, 1, 3, 4);
+        assert_eq!(Point2::new(1, 4), b.clamp(Point2::new(-5, 9)));
+        assert_eq!(Point2::new(2, 2), b.clamp(Point2::new(2, 2)));
+        assert_eq!(Point2::new(3, 1), b.wrap(Point2::new(0, 5)));
+        assert_eq!(Point2::new(1, 4), b.wrap(Point2::new(4, 0)));
+        assert_eq!(Point2::new(2, 3), b.wrap(Point2::new(8, 11)));
+    }
+
+    #[test]
+    fn points() {
+        assert_eq!(
+            vec![
+                Point2::new(0, 0),
+                Point2::new(1, 0),
+                Point2::new(0, 1),
+                Point2::new(1, 1)
+            ],
+            rect(0, 0, 1, 1).points().collect::<Vec<_>>()
+        );
+        assert_eq!(0, rect(1, 0, 0, 0).points().count());
+
+        let b = Bounds3::new(Point3::new(0, 0, 0), Point3::new(2, 1, 3));
+        assert_eq!(24, b.volume());
+        let points: Vec<_> = b.points().collect();
+        assert_eq!(24, points.len());
+        assert!(points
+            .windows(2)
+            .all(|w| (w[0].z, w[0].y, w[0].x) < (w[1].z, w[1].y, w[1].x)));
+        assert!(points.iter().all(|p| b.contains(p)));
+    }
+
+    #[test]
+    fn bounds3() {
+        let b = Bounds3::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+        assert_eq!(
+            Bounds3::new(Point3::new(-1, -1, -1), Point3::new(3, 3, 3)),
+            b.outset(1)
+        );
+        assert_eq!(1, b.inset(1).volume());
+        let c = Bounds3::new(Point3::new(1, 1, 1), Point3::new(5, 5, 5));
+        assert_eq!(
+            Some(Bounds3::new(Point3::new(1, 1, 1), Point3::new(2, 2, 2))),
+            b.intersection(&c)
+        );
+        assert_eq!(
+            Bounds3::new(Point3::new(0, 0, 0), Point3::new(5, 5, 5)),
+            b.union(&c)
+        );
+        assert_eq!(Point3::new(2, 0, 1), b.wrap(Point3::new(-1, 3, 7)));
+        assert_eq!(Point3::new(2, 0, 2), b.clamp(Point3::new(5, -5, 2)));
+        assert_eq!("(0, 0, 0)..=(2, 2, 2)", b.to_string());
+        assert_eq!("(0, 1)..=(2, 3)", rect(0, 1, 2, 3).to_string());
+    }
+}
@@ -0,0 +1,272 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Coord, NeighborMask, Vector2};
+use crate::{oops, oops::Oops};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// The four compass directions. As with the rest of `geometry`, y increases downwards, so north is
+// y - 1.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    // In clockwise order, starting from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    #[must_use]
+    pub fn vector<T: Coord>(self) -> Vector2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Self::North => Vector2::new(zero, -one),
+            Self::East => Vector2::new(one, zero),
+            Self::South => Vector2::new(zero, one),
+            Self::West => Vector2::new(-one, zero),
+        }
+    }
+
+    // The score AoC assigns to facing in this direction: 0 for east, increasing clockwise.
+    #[must_use]
+    pub fn facing(self) -> i32 {
+        (self as i32 + 3) % 4
+    }
+
+    #[must_use]
+    pub fn arrow(self) -> char {
+        match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+        }
+    }
+}
+
+// Accepts arrows (`^>v<`), compass points (`NESW`), or relative moves (`UDLR`).
+impl TryFrom<char> for Direction4 {
+    type Error = Oops;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Self::North),
+            '>' | 'E' | 'R' => Ok(Self::East),
+            'v' | 'S' | 'D' => Ok(Self::South),
+            '<' | 'W' | 'L' => Ok(Self::West),
+            _ => Err(oops!("bad direction {c:?}")),
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(oops!("bad direction {s:?}")),
+        }
+    }
+}
+
+impl Display for Direction4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl<T: Coord> From<Direction4> for Vector2<T> {
+    fn from(d: Direction4) -> Self {
+        d.vector()
+    }
+}
+
+// The four compass directions plus the diagonals between them.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    // In clockwise order, starting from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    // Turns by 90 degrees, like `Direction4`.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 6) % 8]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 2) % 8]
+    }
+
+    #[must_use]
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    // Turns by 45 degrees, e.g. from north to north-west.
+    #[must_use]
+    pub fn turn_left_45(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[must_use]
+    pub fn turn_right_45(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    #[must_use]
+    pub fn vector<T: Coord>(self) -> Vector2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Self::North => Vector2::new(zero, -one),
+            Self::NorthEast => Vector2::new(one, -one),
+            Self::East => Vector2::new(one, zero),
+            Self::SouthEast => Vector2::new(one, one),
+            Self::South => Vector2::new(zero, one),
+            Self::SouthWest => Vector2::new(-one, one),
+            Self::West => Vector2::new(-one, zero),
+            Self::NorthWest => Vector2::new(-one, -one),
+        }
+    }
+}
+
+// Only the four cardinal directions have a single character form.
+impl TryFrom<char> for Direction8 {
+    type Error = Oops;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Direction4::try_from(c).map(Self::from)
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = Oops;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Self::NorthEast),
+            "SE" => Ok(Self::SouthEast),
+            "SW" => Ok(Self::SouthWest),
+            "NW" => Ok(Self::NorthWest),
+            _ => s.parse::<Direction4>().map(Self::from),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(d: Direction4) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+impl<T: Coord> From<Direction8> for Vector2<T> {
+    fn from(d: Direction8) -> Self {
+        d.vector()
+    }
+}
+
+impl From<Direction8> for NeighborMask {
+    fn from(d: Direction8) -> Self {
+        NeighborMask(1 << d as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        for d in Direction4::ALL {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(d.turn_around(), d.turn_right().turn_right());
+            assert_eq!(d.vector::<i32>().rotate_left(), d.turn_left().vector());
+            assert_eq!(d.vector::<i32>().rotate_right(), d.turn_right().vector());
+        }
+        for d in Direction8::ALL {
+            assert_eq!(d.turn_right(), d.turn_right_45().turn_right_45());
+            assert_eq!(d, d.turn_left_45().turn_right_45());
+            assert_eq!(-d.vector::<i32>(), d.turn_around().vector());
+        }
+        assert_eq!(Direction4::West, Direction4::North.turn_left());
+        assert_eq!(Direction8::NorthWest, Direction8::North.turn_left_45());
+    }
+
+    #[test]
+    fn parsing() {
+        for (chars, expected) in ["^NU", ">ER", "vSD", "<WL"].iter().zip(Direction4::ALL) {
+            for c in chars.chars() {
+                assert_eq!(expected, Direction4::try_from(c).unwrap());
+            }
+            assert_eq!(expected, expected.to_string().parse().unwrap());
+        }
+        assert!(Direction4::try_from('x').is_err());
+        assert!("NE".parse::<Direction4>().is_err());
+        assert_eq!(Direction8::NorthEast, "NE".parse().unwrap());
+        assert_eq!(Direction8::West, "L".parse().unwrap());
+    }
+
+    #[test]
+    fn facing() {
+        assert_eq!([3, 0, 1, 2], Direction4::ALL.map(Direction4::facing));
+    }
+
+    #[test]
+    fn neighbor_mask() {
+        assert_eq!(NeighborMask::N, Direction8::North.into());
+        assert_eq!(NeighborMask::SW, Direction8::SouthWest.into());
+        assert_eq!(NeighborMask::NW, Direction8::NorthWest.into());
+    }
+}
@@ -0,0 +1,345 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Bounds2, Point2};
+use crate::{oops, oops::Context, oops::Oops};
+use std::fmt::{Display, Formatter};
+use std::iter::StepBy;
+use std::ops::{Index, IndexMut};
+use std::slice::{ChunksExact, Iter};
+
+// A dense, rectangular grid stored in row-major order. The top-left cell is at (0, 0), with x
+// increasing to the right and y increasing downwards, matching how puzzle maps are drawn.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Result<Self, Oops> {
+        check_size(width, height)?;
+        Ok(Grid {
+            data: vec![value; width * height],
+            width,
+            height,
+        })
+    }
+}
+
+// Grids must have at least one cell, and their coordinates must fit in a `Point2`.
+fn check_size(width: usize, height: usize) -> Result<(), Oops> {
+    if width == 0 || height == 0 {
+        return Err(oops!("empty grid"));
+    }
+    if i32::try_from(width).is_err()
+        || i32::try_from(height).is_err()
+        || width.checked_mul(height).is_none()
+    {
+        return Err(oops!("{width}x{height} grid is too large"));
+    }
+    Ok(())
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Result<Self, Oops> {
+        check_size(width, height)?;
+        if data.len() != width * height {
+            return Err(oops!(
+                "{} cells cannot fill a {width}x{height} grid",
+                data.len()
+            ));
+        }
+        Ok(Grid {
+            data,
+            width,
+            height,
+        })
+    }
+
+    // Parses a character map, one row per line, mapping each character with `f`. Every line must
+    // have the same length.
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Self, Oops>
+    where
+        F: FnMut(char) -> Result<T, Oops>,
+    {
+        let mut data = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in s.lines().enumerate() {
+            let start = data.len();
+            for (x, c) in line.chars().enumerate() {
+                data.push(f(c).at_column(x + 1).at_line(y + 1, line)?);
+            }
+            let len = data.len() - start;
+            let expected = *width.get_or_insert(len);
+            if len != expected {
+                return Err(oops!("expected {expected} cells, got {len}")).at_line(y + 1, line);
+            }
+            height += 1;
+        }
+        let width = width.unwrap_or(0);
+        check_size(width, height)?;
+        Ok(Grid {
+            data,
+            width,
+            height,
+        })
+    }
+
+    // Both dimensions were checked to fit in an `i32` on construction.
+    #[must_use]
+    pub fn width(&self) -> i32 {
+        self.width as i32
+    }
+
+    #[must_use]
+    pub fn height(&self) -> i32 {
+        self.height as i32
+    }
+
+    #[must_use]
+    pub fn bounds(&self) -> Bounds2 {
+        Bounds2 {
+            min: Point2::new(0, 0),
+            max: Point2::new(self.width() - 1, self.height() - 1),
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        self.offset(p).is_some()
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.offset(p).map(|i| &self.data[i])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.data[i])
+    }
+
+    // Iterates over row `y` from left to right.
+    pub fn row(&self, y: i32) -> Iter<'_, T> {
+        assert!(y >= 0 && y < self.height(), "row {y} out of bounds");
+        let start = y as usize * self.width;
+        self.data[start..start + self.width].iter()
+    }
+
+    // Iterates over column `x` from top to bottom.
+    pub fn col(&self, x: i32) -> StepBy<Iter<'_, T>> {
+        assert!(x >= 0 && x < self.width(), "column {x} out of bounds");
+        self.data[x as usize..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.data.chunks_exact(self.width)
+    }
+
+    // All points in the grid, in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let (width, height) = (self.width(), self.height());
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.points().zip(self.data.iter())
+    }
+
+    #[must_use]
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<Point2> {
+        self.iter().find_map(|(p, t)| predicate(t).then_some(p))
+    }
+
+    // The orthogonal neighbors of `p` that are inside the grid.
+    pub fn neighbors(&self, p: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        p.bounded_neighbors(&self.bounds())
+            .map(move |q| (q, &self[q]))
+    }
+
+    #[must_use]
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn offset(&self, p: Point2) -> Option<usize> {
+        if p.x < 0 || p.y < 0 || p.x >= self.width() || p.y >= self.height() {
+            None
+        } else {
+            Some(p.y as usize * self.width + p.x as usize)
+        }
+    }
+}
+
+impl<T> IntoIterator for Grid<T> {
+    type Item = (Point2, T);
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            data: self.data.into_iter(),
+            width: self.width,
+            next: 0,
+        }
+    }
+}
+
+// Yields every cell of a grid along with its position, in row-major order.
+pub struct IntoIter<T> {
+    data: std::vec::IntoIter<T>,
+    width: usize,
+    next: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = (Point2, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let t = self.data.next()?;
+        let i = self.next;
+        self.next += 1;
+        Some((
+            Point2::new((i % self.width) as i32, (i / self.width) as i32),
+            t,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.data.size_hint()
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the grid"))
+    }
+}
+
+// Renders one line per row, with no trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for t in row {
+                write!(f, "{t}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>, Oops> {
+        Grid::parse(s, |c| c.to_digit(10).ok_or_else(|| oops!("bad digit {c}")))
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(1, grid[Point2::new(0, 0)]);
+        assert_eq!(6, grid[Point2::new(2, 1)]);
+        assert_eq!(None, grid.get(Point2::new(3, 0)));
+        assert_eq!(None, grid.get(Point2::new(0, -1)));
+        assert_eq!(Some(Point2::new(1, 1)), grid.position(|&d| d == 5));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = digits("123\n4x6\n").unwrap_err();
+        assert_eq!(Some(2), e.location().line);
+        assert_eq!(Some(2), e.location().column);
+
+        let e = digits("123\n45\n").unwrap_err();
+        assert_eq!(Some(2), e.location().line);
+
+        assert!(digits("").is_err());
+        assert!(digits("\n\n").is_err());
+    }
+
+    #[test]
+    fn size_checks() {
+        assert!(Grid::new(0, 3, 0).is_err());
+        assert!(Grid::new(3, 0, 0).is_err());
+        assert!(Grid::<u8>::from_vec(0, 0, vec![]).is_err());
+        assert!(Grid::from_vec(1 << 31, 1, vec![0]).is_err());
+        let grid = Grid::new(2, 3, 0).unwrap();
+        assert_eq!((2, 3), (grid.width(), grid.height()));
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!(vec![4, 5, 6], grid.row(1).copied().collect::<Vec<_>>());
+        assert_eq!(
+            vec![6, 5, 4],
+            grid.row(1).rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![3, 6], grid.col(2).copied().collect::<Vec<_>>());
+        assert_eq!(vec![5, 2], grid.col(1).rev().copied().collect::<Vec<_>>());
+        assert_eq!(2, grid.col(0).len());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!(
+            vec![(Point2::new(1, 0), &2), (Point2::new(0, 1), &4)],
+            grid.neighbors(Point2::new(0, 0)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn into_iter() {
+        let grid = digits("12\n34\n").unwrap();
+        assert_eq!(
+            vec![
+                (Point2::new(0, 0), 1),
+                (Point2::new(1, 0), 2),
+                (Point2::new(0, 1), 3),
+                (Point2::new(1, 1), 4)
+            ],
+            grid.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let mut grid = digits("123\n456\n").unwrap();
+        grid[Point2::new(1, 0)] = 0;
+        assert_eq!("103\n456", grid.to_string());
+    }
+}
@@ -0,0 +1,625 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod bit_grid;
+mod bounds;
+mod direction;
+mod grid;
+mod sparse_grid;
+
+pub use bit_grid::{BitGrid, NeighborMask};
+pub use bounds::{Bounds2, Bounds3, Points2, Points3};
+pub use direction::{Direction4, Direction8};
+pub use grid::Grid;
+pub use sparse_grid::SparseGrid;
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::iter::Copied;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::slice;
+
+// The numeric types that can be used as coordinates. The geometry types default to `i32`; days
+// whose coordinates overflow that can use the `i64` aliases instead.
+pub trait Coord:
+    Copy
+    + Debug
+    + Display
+    + Hash
+    + Ord
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    #[must_use]
+    fn abs(self) -> Self;
+    #[must_use]
+    fn signum(self) -> Self;
+    #[must_use]
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+// Implements the arithmetic shared by `Vector2` and `Vector3`, as well as translating the
+// corresponding point type by a vector.
+macro_rules! impl_vector {
+    ($vector:ident, $point:ident, $($f:ident),+) => {
+        impl<T: Coord> $vector<T> {
+            pub const ZERO: Self = $vector { $($f: T::ZERO),+ };
+
+            #[must_use]
+            pub fn dot(self, rhs: Self) -> T {
+                T::ZERO $(+ self.$f * rhs.$f)+
+            }
+
+            // The L1 length, i.e. the number of orthogonal steps needed to cover this vector.
+            #[must_use]
+            pub fn manhattan(self) -> T {
+                T::ZERO $(+ self.$f.abs())+
+            }
+
+            // The L∞ length, i.e. the number of steps needed if diagonal steps are allowed.
+            #[must_use]
+            pub fn chebyshev(self) -> T {
+                T::ZERO $(.max(self.$f.abs()))+
+            }
+
+            // Clamps each component to -1, 0, or 1: a single (possibly diagonal) step in the
+            // direction of this vector.
+            #[must_use]
+            pub fn signum(self) -> Self {
+                $vector { $($f: self.$f.signum()),+ }
+            }
+        }
+
+        impl<T: Coord> Neg for $vector<T> {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                $vector { $($f: -self.$f),+ }
+            }
+        }
+
+        impl<T: Coord> Add for $vector<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                $vector { $($f: self.$f + rhs.$f),+ }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $vector<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$f += rhs.$f;)+
+            }
+        }
+
+        impl<T: Coord> Sub for $vector<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                $vector { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+
+        impl<T: Coord> SubAssign for $vector<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$f -= rhs.$f;)+
+            }
+        }
+
+        impl<T: Coord> Mul<T> for $vector<T> {
+            type Output = Self;
+            fn mul(self, rhs: T) -> Self::Output {
+                $vector { $($f: self.$f * rhs),+ }
+            }
+        }
+
+        // Integer division, so each component truncates towards zero.
+        impl<T: Coord> Div<T> for $vector<T> {
+            type Output = Self;
+            fn div(self, rhs: T) -> Self::Output {
+                $vector { $($f: self.$f / rhs),+ }
+            }
+        }
+
+        impl<T: Coord> Add<$vector<T>> for $point<T> {
+            type Output = Self;
+            fn add(self, rhs: $vector<T>) -> Self::Output {
+                $point { $($f: self.$f + rhs.$f),+ }
+            }
+        }
+
+        impl<T: Coord> AddAssign<$vector<T>> for $point<T> {
+            fn add_assign(&mut self, rhs: $vector<T>) {
+                $(self.$f += rhs.$f;)+
+            }
+        }
+
+        impl<T: Coord> Sub<$vector<T>> for $point<T> {
+            type Output = Self;
+            fn sub(self, rhs: $vector<T>) -> Self::Output {
+                $point { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+
+        impl<T: Coord> SubAssign<$vector<T>> for $point<T> {
+            fn sub_assign(&mut self, rhs: $vector<T>) {
+                $(self.$f -= rhs.$f;)+
+            }
+        }
+
+        impl<T: Coord> Sub for $point<T> {
+            type Output = $vector<T>;
+            fn sub(self, rhs: Self) -> Self::Output {
+                $vector { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+    };
+}
+
+impl_vector!(Vector2, Point2, x, y);
+impl_vector!(Vector3, Point3, x, y, z);
+
+pub type Point2I64 = Point2<i64>;
+pub type Vector2I64 = Vector2<i64>;
+pub type Bounds2I64 = Bounds2<i64>;
+pub type Point3I64 = Point3<i64>;
+pub type Vector3I64 = Vector3<i64>;
+pub type Bounds3I64 = Bounds3<i64>;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Point2<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Point2<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    // The four orthogonal neighbors.
+    #[must_use]
+    pub fn neighbors(&self) -> Neighbors2<T> {
+        Neighbors2::new(*self, Vector2::orthogonal().into_iter(), None)
+    }
+
+    // The eight orthogonal and diagonal neighbors, in row-major order.
+    #[must_use]
+    pub fn neighbors8(&self) -> Neighbors2<T, std::array::IntoIter<Vector2<T>, 8>> {
+        Neighbors2::new(*self, Vector2::all_around().into_iter(), None)
+    }
+
+    // The neighbors at each of `offsets`, in the order given.
+    #[must_use]
+    pub fn neighbors_with<'a>(
+        &self,
+        offsets: &'a [Vector2<T>],
+    ) -> Neighbors2<T, Copied<slice::Iter<'a, Vector2<T>>>> {
+        Neighbors2::new(*self, offsets.iter().copied(), None)
+    }
+
+    // Like `neighbors()`, but skips any neighbor outside of `bounds`.
+    #[must_use]
+    pub fn bounded_neighbors(&self, bounds: &Bounds2<T>) -> Neighbors2<T> {
+        Neighbors2::new(*self, Vector2::orthogonal().into_iter(), Some(*bounds))
+    }
+
+    #[must_use]
+    pub fn bounded_neighbors8(
+        &self,
+        bounds: &Bounds2<T>,
+    ) -> Neighbors2<T, std::array::IntoIter<Vector2<T>, 8>> {
+        Neighbors2::new(*self, Vector2::all_around().into_iter(), Some(*bounds))
+    }
+
+    #[must_use]
+    pub fn bounded_neighbors_with<'a>(
+        &self,
+        offsets: &'a [Vector2<T>],
+        bounds: &Bounds2<T>,
+    ) -> Neighbors2<T, Copied<slice::Iter<'a, Vector2<T>>>> {
+        Neighbors2::new(*self, offsets.iter().copied(), Some(*bounds))
+    }
+}
+
+pub struct Neighbors2<T = i32, I = std::array::IntoIter<Vector2<T>, 4>> {
+    p: Point2<T>,
+    offsets: I,
+    bounds: Option<Bounds2<T>>,
+}
+
+impl<T, I> Neighbors2<T, I> {
+    fn new(p: Point2<T>, offsets: I, bounds: Option<Bounds2<T>>) -> Self {
+        Neighbors2 { p, offsets, bounds }
+    }
+}
+
+impl<T: Coord, I: Iterator<Item = Vector2<T>>> Iterator for Neighbors2<T, I> {
+    type Item = Point2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let p = self.p + self.offsets.next()?;
+            if self.bounds.is_none_or(|b| b.contains(&p)) {
+                return Some(p);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Vector2<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Vector2<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    fn orthogonal() -> [Self; 4] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            Self::new(-one, zero),
+            Self::new(one, zero),
+            Self::new(zero, -one),
+            Self::new(zero, one),
+        ]
+    }
+
+    fn all_around() -> [Self; 8] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            Self::new(-one, -one),
+            Self::new(zero, -one),
+            Self::new(one, -one),
+            Self::new(-one, zero),
+            Self::new(one, zero),
+            Self::new(-one, one),
+            Self::new(zero, one),
+            Self::new(one, one),
+        ]
+    }
+
+    // Rotations assume screen coordinates, where y grows downwards, so turning right (clockwise)
+    // takes east (1, 0) to south (0, 1).
+    #[must_use]
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    #[must_use]
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point3<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    // The six orthogonal neighbors.
+    #[must_use]
+    pub fn neighbors(&self) -> Neighbors3<T> {
+        Neighbors3::new(*self, Vector3::orthogonal().into_iter(), None)
+    }
+
+    // All 26 neighbors that share a face, edge, or corner, ordered by z, then y, then x.
+    #[must_use]
+    pub fn neighbors26(&self) -> Neighbors3<T, std::array::IntoIter<Vector3<T>, 26>> {
+        Neighbors3::new(*self, Vector3::all_around().into_iter(), None)
+    }
+
+    // The neighbors at each of `offsets`, in the order given.
+    #[must_use]
+    pub fn neighbors_with<'a>(
+        &self,
+        offsets: &'a [Vector3<T>],
+    ) -> Neighbors3<T, Copied<slice::Iter<'a, Vector3<T>>>> {
+        Neighbors3::new(*self, offsets.iter().copied(), None)
+    }
+
+    // Like `neighbors()`, but skips any neighbor outside of `bounds`.
+    #[must_use]
+    pub fn bounded_neighbors(&self, bounds: &Bounds3<T>) -> Neighbors3<T> {
+        Neighbors3::new(*self, Vector3::orthogonal().into_iter(), Some(*bounds))
+    }
+
+    #[must_use]
+    pub fn bounded_neighbors26(
+        &self,
+        bounds: &Bounds3<T>,
+    ) -> Neighbors3<T, std::array::IntoIter<Vector3<T>, 26>> {
+        Neighbors3::new(*self, Vector3::all_around().into_iter(), Some(*bounds))
+    }
+
+    #[must_use]
+    pub fn bounded_neighbors_with<'a>(
+        &self,
+        offsets: &'a [Vector3<T>],
+        bounds: &Bounds3<T>,
+    ) -> Neighbors3<T, Copied<slice::Iter<'a, Vector3<T>>>> {
+        Neighbors3::new(*self, offsets.iter().copied(), Some(*bounds))
+    }
+}
+
+pub struct Neighbors3<T = i32, I = std::array::IntoIter<Vector3<T>, 6>> {
+    p: Point3<T>,
+    offsets: I,
+    bounds: Option<Bounds3<T>>,
+}
+
+impl<T, I> Neighbors3<T, I> {
+    fn new(p: Point3<T>, offsets: I, bounds: Option<Bounds3<T>>) -> Self {
+        Neighbors3 { p, offsets, bounds }
+    }
+}
+
+impl<T: Coord, I: Iterator<Item = Vector3<T>>> Iterator for Neighbors3<T, I> {
+    type Item = Point3<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let p = self.p + self.offsets.next()?;
+            if self.bounds.is_none_or(|b| b.contains(&p)) {
+                return Some(p);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Vector3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Vector3<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vector3 { x, y, z }
+    }
+
+    fn orthogonal() -> [Self; 6] {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            Self::new(-one, zero, zero),
+            Self::new(one, zero, zero),
+            Self::new(zero, -one, zero),
+            Self::new(zero, one, zero),
+            Self::new(zero, zero, -one),
+            Self::new(zero, zero, one),
+        ]
+    }
+
+    fn all_around() -> [Self; 26] {
+        let steps = [-T::ONE, T::ZERO, T::ONE];
+        // Skip the 14th offset, which is (0, 0, 0).
+        std::array::from_fn(|i| {
+            let i = if i < 13 { i } else { i + 1 };
+            Self::new(steps[i % 3], steps[i / 3 % 3], steps[i / 9])
+        })
+    }
+
+    // Quarter turns about each axis, counterclockwise when looking from the positive end of the
+    // axis towards the origin (i.e. following the right-hand rule).
+    #[must_use]
+    pub fn rotate_x(self) -> Self {
+        Self::new(self.x, -self.z, self.y)
+    }
+
+    #[must_use]
+    pub fn rotate_y(self) -> Self {
+        Self::new(self.z, self.y, -self.x)
+    }
+
+    #[must_use]
+    pub fn rotate_z(self) -> Self {
+        Self::new(-self.y, self.x, self.z)
+    }
+
+    #[must_use]
+    pub fn cross(self, rhs: Self) -> Self {
+        Self::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn i64_coordinates() {
+        let p = Point2I64::new(4_000_000_000, -3);
+        let q = p + Vector2::new(1, 1);
+        assert_eq!(Vector2::new(1, 1), q - p);
+        let bounds = Bounds2I64::from_points([p, q]);
+        assert_eq!(2, bounds.width());
+        assert!(bounds.contains(&Point2::new(4_000_000_001, -3)));
+    }
+
+    #[test]
+    fn neighbors() {
+        assert_eq!(
+            vec![
+                Point2I64::new(-1, 0),
+                Point2::new(1, 0),
+                Point2::new(0, -1),
+                Point2::new(0, 1)
+            ],
+            Point2I64::new(0, 0).neighbors().collect::<Vec<_>>()
+        );
+        assert_eq!(6, Point3::new(0, 0, 0).neighbors().count());
+    }
+
+    #[test]
+    fn neighbors8() {
+        let p = Point2::new(5, 5);
+        let neighbors: Vec<_> = p.neighbors8().collect();
+        assert_eq!(8, neighbors.len());
+        assert_eq!(Point2::new(4, 4), neighbors[0]);
+        assert_eq!(Point2::new(6, 6), neighbors[7]);
+        assert!(neighbors.iter().all(|n| (*n - p).chebyshev() == 1));
+
+        let bounds = Bounds2 {
+            min: Point2::new(0, 0),
+            max: Point2::new(5, 5),
+        };
+        assert_eq!(3, p.bounded_neighbors8(&bounds).count());
+        assert_eq!(2, p.bounded_neighbors(&bounds).count());
+    }
+
+    #[test]
+    fn neighbors26() {
+        let p = Point3::new(0, 0, 0);
+        let neighbors: HashSet<_> = p.neighbors26().collect();
+        assert_eq!(26, neighbors.len());
+        assert!(!neighbors.contains(&p));
+        assert!(neighbors.iter().all(|n| (*n - p).chebyshev() == 1));
+
+        let bounds = Bounds3 {
+            min: Point3::new(0, 0, 0),
+            max: Point3::new(9, 9, 9),
+        };
+        assert_eq!(7, p.bounded_neighbors26(&bounds).count());
+        assert_eq!(3, p.bounded_neighbors(&bounds).count());
+    }
+
+    #[test]
+    fn neighbors_with() {
+        let knight = [Vector2::new(1, 2), Vector2::new(2, 1), Vector2::new(-1, -2)];
+        let p = Point2::new(0, 0);
+        assert_eq!(
+            vec![Point2::new(1, 2), Point2::new(2, 1), Point2::new(-1, -2)],
+            p.neighbors_with(&knight).collect::<Vec<_>>()
+        );
+        let bounds = Bounds2 {
+            min: Point2::new(0, 0),
+            max: Point2::new(1, 2),
+        };
+        assert_eq!(
+            vec![Point2::new(1, 2)],
+            p.bounded_neighbors_with(&knight, &bounds)
+                .collect::<Vec<_>>()
+        );
+        let up = [Vector3::new(0, 0, 1)];
+        assert_eq!(
+            Some(Point3::new(0, 0, 1)),
+            Point3::new(0, 0, 0).neighbors_with(&up).next()
+        );
+    }
+
+    #[test]
+    fn vector2_algebra() {
+        let v = Vector2::new(3, -4);
+        assert_eq!(Vector2::new(-3, 4), -v);
+        assert_eq!(Vector2::new(6, -8), v * 2);
+        assert_eq!(Vector2::new(1, -2), v / 2);
+        assert_eq!(Vector2::new(4, -2), v + Vector2::new(1, 2));
+        assert_eq!(Vector2::ZERO, v - v);
+        assert_eq!(-5, v.dot(Vector2::new(1, 2)));
+        assert_eq!(7, v.manhattan());
+        assert_eq!(4, v.chebyshev());
+        assert_eq!(Vector2::new(1, -1), v.signum());
+
+        let p = Point2::new(1, 1);
+        assert_eq!(Point2::new(-2, 5), p - v);
+        assert_eq!(p, p + v - v);
+    }
+
+    #[test]
+    fn vector2_rotation() {
+        let east = Vector2::new(1, 0);
+        let south = east.rotate_right();
+        assert_eq!(Vector2::new(0, 1), south);
+        assert_eq!(Vector2::new(0, -1), east.rotate_left());
+        assert_eq!(east, south.rotate_left());
+        assert_eq!(-east, south.rotate_right());
+    }
+
+    #[test]
+    fn vector3_algebra() {
+        let v = Vector3I64::new(1, -2, 3);
+        assert_eq!(Vector3::new(-1, 2, -3), -v);
+        assert_eq!(Vector3::new(2, -4, 6), v * 2);
+        assert_eq!(6, v.manhattan());
+        assert_eq!(3, v.chebyshev());
+        assert_eq!(14, v.dot(v));
+        assert_eq!(Vector3::new(1, -1, 1), v.signum());
+        assert_eq!(Point3::new(1, 1, 1) - Point3::new(0, 3, -2), v);
+
+        let (x, y, z) = (
+            Vector3::new(1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, 0, 1),
+        );
+        assert_eq!(z, x.cross(y));
+        assert_eq!(z, y.rotate_x());
+        assert_eq!(x, z.rotate_y());
+        assert_eq!(y, x.rotate_z());
+        assert_eq!(v, v.rotate_z().rotate_z().rotate_z().rotate_z());
+    }
+}
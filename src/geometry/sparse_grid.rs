@@ -0,0 +1,210 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::geometry::{Bounds2, Grid, Point2};
+use std::collections::HashMap;
+
+// A grid that only stores the cells that have been set, for simulations that grow without a known
+// size up front. The bounds are updated as cells are inserted, so they always cover every cell
+// that has ever been set; removing a cell does not shrink them.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    bounds: Option<Bounds2>,
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // `None` until the first cell is inserted.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds2> {
+        self.bounds
+    }
+
+    #[must_use]
+    pub fn contains(&self, p: Point2) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn insert(&mut self, p: Point2, t: T) -> Option<T> {
+        self.grow(p);
+        self.cells.insert(p, t)
+    }
+
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&mut self, p: Point2, f: F) -> &mut T {
+        self.grow(p);
+        self.cells.entry(p).or_insert_with(f)
+    }
+
+    pub fn remove(&mut self, p: Point2) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    // Iterates over the set cells in row-major order. The cells are sorted on every call, which
+    // costs O(n log n) up front, so avoid calling this inside a loop; `get` and `contains` are the
+    // cheap way to look at individual cells.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        let mut cells: Vec<_> = self.cells.iter().map(|(p, t)| (*p, t)).collect();
+        cells.sort_unstable_by_key(|(p, _)| (p.y, p.x));
+        cells.into_iter()
+    }
+
+    // Draws every cell within the bounds, one line per row with no trailing newline. Unset cells
+    // are drawn as `empty`.
+    pub fn render<F: Fn(&T) -> char>(&self, glyph: F, empty: char) -> String {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+        (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| self.get(Point2::new(x, y)).map_or(empty, &glyph))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn grow(&mut self, p: Point2) {
+        self.bounds = Some(match self.bounds {
+            Some(b) => b.union(&Bounds2::new(p, p)),
+            None => Bounds2::new(p, p),
+        });
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    // Copies the cells into a dense grid covering the bounds, filling unset cells with `fill`.
+    // The top-left cell of the dense grid corresponds to `bounds().min`. Returns `None` if the
+    // grid is empty.
+    #[must_use]
+    pub fn to_grid(&self, fill: T) -> Option<Grid<T>> {
+        let bounds = self.bounds?;
+        let mut grid = Grid::new(bounds.width() as usize, bounds.height() as usize, fill)
+            .expect("bounds cover at least one cell");
+        for (p, t) in &self.cells {
+            grid[Point2::new(p.x - bounds.min.x, p.y - bounds.min.y)] = t.clone();
+        }
+        Some(grid)
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        grid.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<(Point2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point2, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point2, T)>>(&mut self, iter: I) {
+        for (p, t) in iter {
+            self.insert(p, t);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_grow() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+        grid.insert(Point2::new(3, -2), 'a');
+        grid.insert(Point2::new(-1, 4), 'b');
+        assert_eq!(
+            Some(Bounds2 {
+                min: Point2::new(-1, -2),
+                max: Point2::new(3, 4)
+            }),
+            grid.bounds()
+        );
+        assert_eq!(2, grid.len());
+    }
+
+    #[test]
+    fn row_major_iteration() {
+        let grid: SparseGrid<_> = [
+            (Point2::new(1, 1), 'd'),
+            (Point2::new(5, 0), 'b'),
+            (Point2::new(0, 1), 'c'),
+            (Point2::new(-2, 0), 'a'),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!("abcd", grid.iter().map(|(_, c)| *c).collect::<String>());
+    }
+
+    #[test]
+    fn render() {
+        let grid: SparseGrid<_> = [(Point2::new(10, 10), true), (Point2::new(12, 11), false)]
+            .into_iter()
+            .collect();
+        assert_eq!("#..\n..o", grid.render(|&t| if t { '#' } else { 'o' }, '.'));
+        assert_eq!("", SparseGrid::<bool>::new().render(|_| '#', '.'));
+    }
+
+    #[test]
+    fn grid_round_trip() {
+        let dense = Grid::parse("ab\ncd", Ok).unwrap();
+        let sparse = SparseGrid::from(dense.clone());
+        assert_eq!(Some('d'), sparse.get(Point2::new(1, 1)).copied());
+        assert_eq!(Some(dense), sparse.to_grid(' '));
+
+        let sparse: SparseGrid<_> = [(Point2::new(-1, 5), 'x')].into_iter().collect();
+        assert_eq!("x", sparse.to_grid('.').unwrap().to_string());
+    }
+}
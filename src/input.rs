@@ -0,0 +1,133 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::{oops, oops::Oops};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// If set, puzzle inputs are looked up in this directory instead of `inputs/` under the current
+// directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    Default(u32),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    // Interprets an optional command-line argument: no argument selects the default input for
+    // `day`, `-` selects stdin, and anything else is treated as a path.
+    #[must_use]
+    pub fn from_arg(day: u32, arg: Option<&str>) -> Self {
+        match arg {
+            None => InputSource::Default(day),
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    pub fn load(&self) -> Result<String, Oops> {
+        let (input, name) = match self {
+            InputSource::Default(day) => {
+                let path = default_path(*day);
+                (read_file(&path)?, path.display().to_string())
+            }
+            InputSource::Path(path) => (read_file(path)?, path.display().to_string()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                (input, "stdin".to_string())
+            }
+        };
+        if input.trim().is_empty() {
+            return Err(oops!("empty input from {name}"));
+        }
+        Ok(input)
+    }
+}
+
+#[must_use]
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR).map_or_else(|| PathBuf::from("inputs"), PathBuf::from)
+}
+
+#[must_use]
+pub fn default_path(day: u32) -> PathBuf {
+    input_dir().join(format!("day{day:02}.txt"))
+}
+
+fn read_file(path: &Path) -> Result<String, Oops> {
+    fs::read_to_string(path).map_err(|e| oops!("cannot read input {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(InputSource::Default(3), InputSource::from_arg(3, None));
+        assert_eq!(InputSource::Stdin, InputSource::from_arg(3, Some("-")));
+        assert_eq!(
+            InputSource::Path(PathBuf::from("foo.txt")),
+            InputSource::from_arg(3, Some("foo.txt"))
+        );
+    }
+
+    #[test]
+    fn default_path_is_zero_padded() {
+        assert!(default_path(7).ends_with("day07.txt"));
+    }
+
+    #[test]
+    fn missing_file() {
+        let dir = TempDir::new("input_missing");
+        assert!(InputSource::Path(dir.join("missing.txt")).load().is_err());
+    }
+
+    #[test]
+    fn empty_file() {
+        let dir = TempDir::new("input_empty");
+        let path = dir.join("empty.txt");
+        fs::write(&path, "\n").unwrap();
+        assert!(InputSource::Path(path).load().is_err());
+    }
+
+    #[test]
+    fn explicit_path() {
+        let dir = TempDir::new("input_explicit");
+        let path = dir.join("explicit.txt");
+        fs::write(&path, "1\n2\n").unwrap();
+        assert_eq!("1\n2\n", InputSource::Path(path).load().unwrap());
+    }
+
+    // The only test that sets the variable, so it cannot race with the others.
+    #[test]
+    fn input_dir_override() {
+        let dir = TempDir::new("input_override");
+        fs::write(dir.join("day07.txt"), "7\n").unwrap();
+        env::set_var(INPUT_DIR_VAR, dir.path());
+        let loaded = InputSource::Default(7).load();
+        let path = default_path(7);
+        env::remove_var(INPUT_DIR_VAR);
+        assert_eq!(dir.join("day07.txt"), path);
+        assert_eq!("7\n", loaded.unwrap());
+        assert_eq!(Path::new("inputs/day07.txt"), default_path(7));
+    }
+}
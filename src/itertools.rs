@@ -12,6 +12,11 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use crate::{oops, oops::Oops};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
 pub struct Segmenter<I: Iterator, P> {
     iter: I,
     predicate: P,
@@ -43,11 +48,240 @@ where
     }
 }
 
+// Like `Segmenter`, but yields subslices of the original slice rather than collecting each segment
+// into a new `Vec`.
+pub struct SliceSegmenter<'a, T, P> {
+    items: &'a [T],
+    predicate: P,
+}
+
+impl<'a, T, P> Iterator for SliceSegmenter<'a, T, P>
+where
+    P: FnMut(&T) -> bool,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.items.iter().position(|item| !(self.predicate)(item))?;
+        let items = &self.items[start..];
+        let len = items
+            .iter()
+            .position(|item| (self.predicate)(item))
+            .unwrap_or(items.len());
+        let (segment, rest) = items.split_at(len);
+        self.items = rest;
+        Some(segment)
+    }
+}
+
+pub trait SliceTools<T> {
+    // Splits the slice at every item matching `predicate`, skipping empty segments.
+    fn segments<P>(&self, predicate: P) -> SliceSegmenter<'_, T, P>
+    where
+        P: FnMut(&T) -> bool;
+}
+
+impl<T> SliceTools<T> for [T] {
+    fn segments<P>(&self, predicate: P) -> SliceSegmenter<'_, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        SliceSegmenter {
+            items: self,
+            predicate,
+        }
+    }
+}
+
+// The blocks of lines in a string that are separated by blank lines. Each block borrows from the
+// original string and omits the line ending after its last line.
+pub struct Paragraphs<'a> {
+    rest: &'a str,
+    // The 1-based line number that `rest` starts on.
+    line: usize,
+}
+
+// Splits off the first line, returning it without its line ending, and the remainder.
+fn split_line(s: &str) -> (&str, &str) {
+    let (line, rest) = s.split_once('\n').unwrap_or((s, ""));
+    (line.strip_suffix('\r').unwrap_or(line), rest)
+}
+
+impl<'a> Paragraphs<'a> {
+    // Like `next()`, but also returns the 1-based line number that the paragraph starts on.
+    fn next_numbered(&mut self) -> Option<(usize, &'a str)> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (line, rest) = split_line(self.rest);
+            if !line.trim().is_empty() {
+                break;
+            }
+            self.rest = rest;
+            self.line += 1;
+        }
+        let (start, first_line) = (self.rest, self.line);
+        let mut len = 0;
+        while !self.rest.is_empty() {
+            let (line, rest) = split_line(self.rest);
+            if line.trim().is_empty() {
+                break;
+            }
+            len = start.len() - self.rest.len() + line.len();
+            self.rest = rest;
+            self.line += 1;
+        }
+        Some((first_line, &start[..len]))
+    }
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_numbered().map(|(_, paragraph)| paragraph)
+    }
+}
+
+// Like `Paragraphs`, but yields each paragraph along with the 1-based line number it starts on, for
+// error messages.
+pub struct NumberedParagraphs<'a>(Paragraphs<'a>);
+
+impl<'a> Iterator for NumberedParagraphs<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_numbered()
+    }
+}
+
+pub trait StrTools {
+    // Splits the string into blank-line-separated blocks without copying. Runs of blank lines are
+    // treated as a single separator, and blank lines at either end are ignored.
+    fn paragraphs(&self) -> Paragraphs<'_>;
+
+    fn numbered_paragraphs(&self) -> NumberedParagraphs<'_>;
+}
+
+impl StrTools for str {
+    fn paragraphs(&self) -> Paragraphs<'_> {
+        Paragraphs {
+            rest: self,
+            line: 1,
+        }
+    }
+
+    fn numbered_paragraphs(&self) -> NumberedParagraphs<'_> {
+        NumberedParagraphs(self.paragraphs())
+    }
+}
+
+// Groups items into arrays of exactly `N`. If the items run out partway through a group, yields a
+// single error instead of the partial group.
+pub struct ChunksExact<I: Iterator, const N: usize> {
+    iter: I,
+}
+
+impl<I: Iterator, const N: usize> Iterator for ChunksExact<I, N> {
+    type Item = Result<[I::Item; N], Oops>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<_> = self.iter.by_ref().take(N).collect();
+        match chunk.len() {
+            0 => None,
+            len if len < N => Some(Err(oops!("{len} leftover items for a chunk of {N}"))),
+            _ => Some(Ok(to_array(chunk))),
+        }
+    }
+}
+
+// Overlapping windows of `N` consecutive items, e.g. `[a, b, c]`, `[b, c, d]`, ...
+pub struct TupleWindows<I: Iterator, const N: usize> {
+    iter: I,
+    // The most recently yielded window, or the first window if nothing has been yielded yet.
+    window: Option<[I::Item; N]>,
+    started: bool,
+}
+
+impl<I: Iterator, const N: usize> Iterator for TupleWindows<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return self.window.clone();
+        }
+        let window = self.window.as_mut()?;
+        let Some(item) = self.iter.next() else {
+            self.window = None;
+            return None;
+        };
+        window.rotate_left(1);
+        window[N - 1] = item;
+        Some(window.clone())
+    }
+}
+
+fn to_array<T, const N: usize>(v: Vec<T>) -> [T; N] {
+    match v.try_into() {
+        Ok(array) => array,
+        Err(_) => unreachable!("length should have been checked"),
+    }
+}
+
 pub trait IterTools {
     fn segment<P>(self, predicate: P) -> Segmenter<Self, P>
     where
         Self: Iterator + Sized,
         P: FnMut(&Self::Item) -> bool;
+
+    fn chunks_exact<const N: usize>(self) -> ChunksExact<Self, N>
+    where
+        Self: Iterator + Sized;
+
+    // Fails if there are fewer than `N` items, i.e. not even one window.
+    fn tuple_windows<const N: usize>(self) -> Result<TupleWindows<Self, N>, Oops>
+    where
+        Self: Iterator + Sized,
+        Self::Item: Clone;
+
+    fn all_unique(self) -> bool
+    where
+        Self: Iterator + Sized,
+        Self::Item: Eq + Hash;
+
+    // The `k` largest items, largest first. Fails if there are fewer than `k` items.
+    fn k_largest(self, k: usize) -> Result<Vec<Self::Item>, Oops>
+    where
+        Self: Iterator + Sized,
+        Self::Item: Ord;
+
+    // The `k` smallest items, smallest first. Fails if there are fewer than `k` items.
+    fn k_smallest(self, k: usize) -> Result<Vec<Self::Item>, Oops>
+    where
+        Self: Iterator + Sized,
+        Self::Item: Ord;
+
+    // How many times each distinct item occurs.
+    fn counts(self) -> HashMap<Self::Item, usize>
+    where
+        Self: Iterator + Sized,
+        Self::Item: Eq + Hash;
+
+    // The smallest and largest items. Fails if there are no items.
+    fn minmax(self) -> Result<(Self::Item, Self::Item), Oops>
+    where
+        Self: Iterator + Sized,
+        Self::Item: Clone + Ord;
+
+    // Fails unless there are exactly `N` items.
+    fn collect_array<const N: usize>(self) -> Result<[Self::Item; N], Oops>
+    where
+        Self: Iterator + Sized;
 }
 
 impl<T: Iterator> IterTools for T {
@@ -60,6 +294,113 @@ impl<T: Iterator> IterTools for T {
             predicate,
         }
     }
+
+    fn chunks_exact<const N: usize>(self) -> ChunksExact<T, N> {
+        ChunksExact { iter: self }
+    }
+
+    fn tuple_windows<const N: usize>(mut self) -> Result<TupleWindows<T, N>, Oops>
+    where
+        T::Item: Clone,
+    {
+        if N == 0 {
+            return Err(oops!("a window needs at least one item"));
+        }
+        let first: Vec<_> = self.by_ref().take(N).collect();
+        if first.len() < N {
+            return Err(oops!(
+                "{} items is too few for a window of {N}",
+                first.len()
+            ));
+        }
+        Ok(TupleWindows {
+            iter: self,
+            window: Some(to_array(first)),
+            started: false,
+        })
+    }
+
+    fn all_unique(mut self) -> bool
+    where
+        T::Item: Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        self.all(|item| seen.insert(item))
+    }
+
+    fn k_largest(self, k: usize) -> Result<Vec<T::Item>, Oops>
+    where
+        T::Item: Ord,
+    {
+        // A min-heap of the largest items so far, so the smallest of them is cheap to evict.
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            heap.push(Reverse(item));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        if heap.len() < k {
+            return Err(oops!("only {} items, wanted the {k} largest", heap.len()));
+        }
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect())
+    }
+
+    fn k_smallest(self, k: usize) -> Result<Vec<T::Item>, Oops>
+    where
+        T::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            heap.push(item);
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        if heap.len() < k {
+            return Err(oops!("only {} items, wanted the {k} smallest", heap.len()));
+        }
+        Ok(heap.into_sorted_vec())
+    }
+
+    fn counts(self) -> HashMap<T::Item, usize>
+    where
+        T::Item: Eq + Hash,
+    {
+        let mut counts = HashMap::new();
+        for item in self {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    fn minmax(mut self) -> Result<(T::Item, T::Item), Oops>
+    where
+        T::Item: Clone + Ord,
+    {
+        let first = self.next().ok_or_else(|| oops!("no items"))?;
+        Ok(self.fold((first.clone(), first), |(min, max), item| {
+            if item < min {
+                (item, max)
+            } else if item > max {
+                (min, item)
+            } else {
+                (min, max)
+            }
+        }))
+    }
+
+    fn collect_array<const N: usize>(self) -> Result<[T::Item; N], Oops> {
+        let items: Vec<_> = self.collect();
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| oops!("expected {N} items but got {len}"))
+    }
 }
 
 #[cfg(test)]
@@ -129,4 +470,218 @@ mod tests {
         assert_eq!(group.into_iter().copied().collect::<Vec<_>>(), &[1]);
         assert_eq!(None, group_iter.next());
     }
+
+    #[test]
+    fn chunks_exact_empty() {
+        assert!(std::iter::empty::<u32>()
+            .chunks_exact::<2>()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn chunks_exact_exact() {
+        let mut chunks = (1..=6).chunks_exact::<3>();
+        assert_eq!([1, 2, 3], chunks.next().unwrap().unwrap());
+        assert_eq!([4, 5, 6], chunks.next().unwrap().unwrap());
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn chunks_exact_short() {
+        let mut chunks = (1..=2).chunks_exact::<3>();
+        assert!(chunks.next().unwrap().is_err());
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn chunks_exact_leftover() {
+        let mut chunks = (1..=4).chunks_exact::<3>();
+        assert_eq!([1, 2, 3], chunks.next().unwrap().unwrap());
+        assert!(chunks.next().unwrap().is_err());
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn tuple_windows_empty() {
+        assert!(std::iter::empty::<u32>().tuple_windows::<1>().is_err());
+    }
+
+    #[test]
+    fn tuple_windows_exact() {
+        let windows: Vec<_> = (1..=3).tuple_windows::<3>().unwrap().collect();
+        assert_eq!(vec![[1, 2, 3]], windows);
+    }
+
+    #[test]
+    fn tuple_windows_short() {
+        assert!((1..=2).tuple_windows::<3>().is_err());
+    }
+
+    #[test]
+    fn tuple_windows_leftover() {
+        let windows: Vec<_> = (1..=4).tuple_windows::<2>().unwrap().collect();
+        assert_eq!(vec![[1, 2], [2, 3], [3, 4]], windows);
+    }
+
+    #[test]
+    fn tuple_windows_zero_width() {
+        assert!((1..=3).tuple_windows::<0>().is_err());
+    }
+
+    #[test]
+    fn all_unique_empty() {
+        assert!(std::iter::empty::<u32>().all_unique());
+    }
+
+    #[test]
+    fn all_unique() {
+        assert!([1, 2, 3].iter().all_unique());
+    }
+
+    #[test]
+    fn all_unique_with_duplicates() {
+        assert!(![1, 2, 1].iter().all_unique());
+        assert!(!"abcdb".chars().all_unique());
+    }
+
+    const UNSORTED: [u32; 7] = [5, 1, 4, 1, 3, 9, 2];
+
+    #[test]
+    fn k_largest_empty() {
+        assert!(std::iter::empty::<u32>().k_largest(1).is_err());
+        assert_eq!(Vec::<u32>::new(), std::iter::empty().k_largest(0).unwrap());
+    }
+
+    #[test]
+    fn k_largest() {
+        assert_eq!(vec![9, 5, 4], UNSORTED.into_iter().k_largest(3).unwrap());
+    }
+
+    #[test]
+    fn k_largest_exact() {
+        assert_eq!(
+            vec![9, 5, 4, 3, 2, 1, 1],
+            UNSORTED.into_iter().k_largest(7).unwrap()
+        );
+    }
+
+    #[test]
+    fn k_largest_too_many() {
+        assert!(UNSORTED.into_iter().k_largest(8).is_err());
+    }
+
+    #[test]
+    fn k_smallest_empty() {
+        assert!(std::iter::empty::<u32>().k_smallest(1).is_err());
+    }
+
+    #[test]
+    fn k_smallest() {
+        assert_eq!(vec![1, 1, 2], UNSORTED.into_iter().k_smallest(3).unwrap());
+    }
+
+    #[test]
+    fn k_smallest_exact() {
+        assert_eq!(
+            vec![1, 1, 2, 3, 4, 5, 9],
+            UNSORTED.into_iter().k_smallest(7).unwrap()
+        );
+    }
+
+    #[test]
+    fn k_smallest_too_many() {
+        assert!(UNSORTED.into_iter().k_smallest(8).is_err());
+    }
+
+    #[test]
+    fn counts_empty() {
+        assert!(std::iter::empty::<u32>().counts().is_empty());
+    }
+
+    #[test]
+    fn counts() {
+        let counts = "hello".chars().counts();
+        assert_eq!(4, counts.len());
+        assert_eq!(Some(&2), counts.get(&'l'));
+        assert_eq!(Some(&1), counts.get(&'h'));
+    }
+
+    #[test]
+    fn minmax_empty() {
+        assert!(std::iter::empty::<u32>().minmax().is_err());
+    }
+
+    #[test]
+    fn minmax_one() {
+        assert_eq!((4, 4), [4].into_iter().minmax().unwrap());
+    }
+
+    #[test]
+    fn minmax() {
+        assert_eq!((1, 9), [5, 1, 9, 3].into_iter().minmax().unwrap());
+    }
+
+    #[test]
+    fn collect_array_empty() {
+        assert_eq!([0u32; 0], std::iter::empty().collect_array().unwrap());
+        assert!(std::iter::empty::<u32>().collect_array::<1>().is_err());
+    }
+
+    #[test]
+    fn collect_array_exact() {
+        assert_eq!(["a", "b"], "a,b".split(',').collect_array().unwrap());
+    }
+
+    #[test]
+    fn collect_array_short() {
+        assert!("a".split(',').collect_array::<2>().is_err());
+    }
+
+    #[test]
+    fn collect_array_leftover() {
+        assert!("a,b,c".split(',').collect_array::<2>().is_err());
+    }
+
+    #[test]
+    fn slice_segments() {
+        let v = [0, 1, 2, 0, 0, 3, 0];
+        let segments: Vec<_> = v.segments(|x| *x == 0).collect();
+        assert_eq!(vec![&[1, 2][..], &[3][..]], segments);
+        assert_eq!(None, [0, 0].segments(|x| *x == 0).next());
+        assert_eq!(None, <[u32]>::segments(&[], |x| *x == 0).next());
+        // Segments borrow from the original slice.
+        let first = v.segments(|x| *x == 0).next().unwrap();
+        assert!(std::ptr::eq(&v[1], &first[0]));
+    }
+
+    #[test]
+    fn paragraphs() {
+        let s = "a\nb\n\nc\n";
+        assert_eq!(vec!["a\nb", "c"], s.paragraphs().collect::<Vec<_>>());
+        assert_eq!(None, "".paragraphs().next());
+        assert_eq!(None, "\n\n".paragraphs().next());
+        assert_eq!(vec!["x"], "x".paragraphs().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn paragraphs_with_extra_blank_lines() {
+        let s = "\n\n  a\n\n\n \nb\nc\n\n";
+        assert_eq!(vec!["  a", "b\nc"], s.paragraphs().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn numbered_paragraphs() {
+        let s = "\n\na\r\n\r\n\n \nb\nc\n\nd";
+        assert_eq!(
+            vec![(3, "a"), (7, "b\nc"), (10, "d")],
+            s.numbered_paragraphs().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn paragraphs_with_crlf() {
+        let s = "a\r\nb\r\n\r\nc\r\n";
+        assert_eq!(vec!["a\r\nb", "c"], s.paragraphs().collect::<Vec<_>>());
+    }
 }
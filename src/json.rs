@@ -0,0 +1,171 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::fmt::{Display, Formatter, Write};
+
+// Just enough JSON to emit machine-readable results without pulling in a serialization crate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    #[must_use]
+    pub fn object<K: Into<String>, I: IntoIterator<Item = (K, Json)>>(fields: I) -> Self {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(n: i64) -> Self {
+        Json::Int(n)
+    }
+}
+
+impl From<u32> for Json {
+    fn from(n: u32) -> Self {
+        Json::Int(n.into())
+    }
+}
+
+// Counts too large for an `i64` fall back to the nearest float, like any other big JSON number.
+impl From<usize> for Json {
+    fn from(n: usize) -> Self {
+        i64::try_from(n).map_or(Json::Float(n as f64), Json::Int)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(n: f64) -> Self {
+        Json::Float(n)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(o: Option<T>) -> Self {
+        o.map_or(Json::Null, Into::into)
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if u32::from(c) < 0x20 => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Int(n) => write!(f, "{n}"),
+            // JSON has no representation for NaN or infinities.
+            Json::Float(n) if !n.is_finite() => f.write_str("null"),
+            Json::Float(n) => write!(f, "{n}"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalars() {
+        assert_eq!("null", Json::Null.to_string());
+        assert_eq!("true", Json::from(true).to_string());
+        assert_eq!("-42", Json::from(-42i64).to_string());
+        assert_eq!("7", Json::from(7usize).to_string());
+        assert_eq!(Json::Float(usize::MAX as f64), Json::from(usize::MAX));
+        assert_eq!("0.5", Json::from(0.5).to_string());
+        assert_eq!("null", Json::from(f64::NAN).to_string());
+        assert_eq!("null", Json::from(None::<i64>).to_string());
+    }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(
+            r#""a\"b\\c\nd\u0001""#,
+            Json::from("a\"b\\c\nd\u{1}").to_string()
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let value = Json::object([
+            ("day", Json::from(10u32)),
+            (
+                "parts",
+                Json::Array(vec![Json::from(1i64), Json::from("#.\n.#")]),
+            ),
+        ]);
+        assert_eq!(r##"{"day":10,"parts":[1,"#.\n.#"]}"##, value.to_string());
+    }
+}
@@ -12,6 +12,17 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+pub mod answers;
+pub mod cycle;
+pub mod days;
 pub mod geometry;
+pub mod input;
 pub mod itertools;
+pub mod json;
 pub mod oops;
+pub mod parallel;
+pub mod scan;
+pub mod search;
+pub mod solution;
+#[cfg(test)]
+mod testing;
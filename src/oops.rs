@@ -12,36 +12,320 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug)]
-pub enum Oops {
+// Where in the puzzle input an error happened. Line and column numbers are 1-based, as in an editor.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Location {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+}
+
+enum Cause {
     Message(String),
-    RealError(Box<dyn std::error::Error>),
+    RealError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+pub struct Oops {
+    cause: Cause,
+    location: Location,
+    // Innermost first, e.g. "while parsing operation", then "while parsing monkey 3".
+    frames: Vec<String>,
+}
+
+impl Oops {
+    #[must_use]
+    pub fn message(message: String) -> Self {
+        Oops::new(Cause::Message(message))
+    }
+
+    // Wraps any other error, e.g. one from another crate. The wrapped error's own source, if any,
+    // stays available through `source()`.
+    #[must_use]
+    pub fn wrap<E>(error: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Oops::new(Cause::RealError(Box::new(error)))
+    }
+
+    fn new(cause: Cause) -> Self {
+        Oops {
+            cause,
+            location: Location::default(),
+            frames: vec![],
+        }
+    }
+
+    // The location setters keep any value that is already set, since the code closest to the
+    // failure knows best where it happened.
+
+    #[must_use]
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.location.line.get_or_insert(line);
+        self
+    }
+
+    #[must_use]
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.location.column.get_or_insert(column);
+        self
+    }
+
+    #[must_use]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.location.text.get_or_insert_with(|| text.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn context<C: Display>(mut self, frame: C) -> Self {
+        self.frames.push(frame.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    #[must_use]
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
 }
 
 impl Display for Oops {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        match self {
-            Oops::Message(s) => write!(f, "oops: {s}")?,
-            Oops::RealError(e) => e.fmt(f)?,
+        match &self.cause {
+            Cause::Message(s) => write!(f, "oops: {s}")?,
+            Cause::RealError(e) => write!(f, "{e}")?,
+        }
+        let Location { line, column, text } = &self.location;
+        match (line, column) {
+            (Some(line), Some(column)) => write!(f, "\n  at line {line}, column {column}")?,
+            (Some(line), None) => write!(f, "\n  at line {line}")?,
+            (None, Some(column)) => write!(f, "\n  at column {column}")?,
+            (None, None) => (),
+        }
+        if let Some(text) = text {
+            write!(f, "\n  | {text}")?;
+            if let Some(column) = column {
+                write!(f, "\n  | {:>column$}", "^")?;
+            }
+        }
+        for frame in &self.frames {
+            write!(f, "\n  {frame}")?;
         }
         Ok(())
     }
 }
 
-impl<E> From<E> for Oops
+// `main` returning `Err` prints the `Debug` representation, so make that as readable as `Display`.
+impl Debug for Oops {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        Display::fmt(self, f)
+    }
+}
+
+// `Display` already shows a wrapped error's message, so the chain continues from that error's own
+// source; otherwise reporters that walk `source()` would print the wrapped error twice.
+impl std::error::Error for Oops {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.cause {
+            Cause::Message(_) => None,
+            Cause::RealError(e) => e.source(),
+        }
+    }
+}
+
+// Now that `Oops` is an `Error` itself, a blanket `From<E: Error>` impl would overlap with the
+// reflexive `From<Oops> for Oops`, so the errors that `?` should convert are listed explicitly.
+macro_rules! from_errors {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl From<$t> for Oops {
+                fn from(error: $t) -> Self {
+                    Oops::wrap(error)
+                }
+            }
+        )*
+    };
+}
+
+from_errors!(
+    std::char::ParseCharError,
+    std::convert::Infallible,
+    std::char::TryFromCharError,
+    std::fmt::Error,
+    std::io::Error,
+    std::num::ParseFloatError,
+    std::num::ParseIntError,
+    std::num::TryFromIntError,
+    std::str::ParseBoolError,
+    std::str::Utf8Error,
+    std::string::FromUtf8Error,
+);
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for Oops {
+    fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Oops::new(Cause::RealError(error))
+    }
+}
+
+// Attaches context to the error of any `Result` whose error converts into an `Oops`.
+pub trait Context<T> {
+    fn context<C: Display>(self, frame: C) -> Result<T, Oops>;
+    fn with_context<C: Display, F: FnOnce() -> C>(self, frame: F) -> Result<T, Oops>;
+    fn at_line(self, line: usize, text: &str) -> Result<T, Oops>;
+    fn at_column(self, column: usize) -> Result<T, Oops>;
+}
+
+impl<T, E: Into<Oops>> Context<T> for Result<T, E> {
+    fn context<C: Display>(self, frame: C) -> Result<T, Oops> {
+        self.map_err(|e| e.into().context(frame))
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, frame: F) -> Result<T, Oops> {
+        self.map_err(|e| e.into().context(frame()))
+    }
+
+    fn at_line(self, line: usize, text: &str) -> Result<T, Oops> {
+        self.map_err(|e| e.into().at_line(line).with_text(text))
+    }
+
+    fn at_column(self, column: usize) -> Result<T, Oops> {
+        self.map_err(|e| e.into().at_column(column))
+    }
+}
+
+// Parses every line of `input` as a `T`, reporting the line number and text of the first line that
+// fails to parse.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, Oops>
 where
-    E: std::error::Error + 'static,
+    T: FromStr,
+    T::Err: Into<Oops>,
 {
-    fn from(error: E) -> Self {
-        Oops::RealError(Box::new(error))
-    }
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().at_line(i + 1, line))
+        .collect()
 }
 
 #[macro_export]
 macro_rules! oops {
     ($($e:expr),*) => {
-        Oops::Message(format!($($e,)*))
+        $crate::oops::Oops::message(format!($($e,)*))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_message() {
+        assert_eq!("oops: bad line", oops!("bad line").to_string());
+    }
+
+    #[test]
+    fn display_context() {
+        let result: Result<i32, _> = "4x2".parse::<i32>();
+        let e = result
+            .at_column(2)
+            .at_line(7, "4x2")
+            .context("while parsing monkey 3")
+            .context("while parsing puzzle")
+            .unwrap_err();
+        assert_eq!(
+            concat!(
+                "invalid digit found in string\n",
+                "  at line 7, column 2\n",
+                "  | 4x2\n",
+                "  |  ^\n",
+                "  while parsing monkey 3\n",
+                "  while parsing puzzle",
+            ),
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn innermost_location_wins() {
+        let e = oops!("bad").at_line(2).at_column(5).at_line(9).at_column(1);
+        assert_eq!(Some(2), e.location().line);
+        assert_eq!(Some(5), e.location().column);
+    }
+
+    #[test]
+    fn source_chain() {
+        use std::error::Error;
+
+        #[derive(Debug)]
+        struct Inner;
+
+        impl Display for Inner {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+                write!(f, "inner failed")
+            }
+        }
+
+        impl Error for Inner {}
+
+        #[derive(Debug)]
+        struct Outer(Inner);
+
+        impl Display for Outer {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+                write!(f, "outer failed")
+            }
+        }
+
+        impl Error for Outer {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let e = Oops::wrap(Outer(Inner)).context("while testing");
+        let chain: Vec<_> = std::iter::successors(Some(&e as &dyn Error), |e| (*e).source())
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(2, chain.len());
+        assert_eq!("outer failed\n  while testing", chain[0]);
+        assert_eq!("inner failed", chain[1]);
+    }
+
+    #[test]
+    fn source_of_leaf_errors() {
+        use std::error::Error;
+
+        fn parse(s: &str) -> Result<i32, Oops> {
+            Ok(s.parse::<i32>()?)
+        }
+
+        let e = parse("x").unwrap_err();
+        assert_eq!("invalid digit found in string", e.to_string());
+        assert!(e.source().is_none());
+        assert!(oops!("bad").source().is_none());
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn check<T: Send + Sync + std::error::Error + 'static>() {}
+        check::<Oops>();
+        let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(oops!("bad"));
+        assert_eq!("oops: bad", boxed.to_string());
+    }
+
+    #[test]
+    fn parse_lines_reports_line() {
+        assert_eq!(vec![1, 2, 3], parse_lines::<i32>("1\n2\n3").unwrap());
+        let e = parse_lines::<i32>("1\nfoo\n3").unwrap_err();
+        assert_eq!(Some(2), e.location().line);
+        assert_eq!(Some("foo"), e.location().text.as_deref());
+    }
+}
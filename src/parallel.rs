@@ -0,0 +1,200 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+#[must_use]
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// Number of threads a solution should use internally when `requested` were asked for. More threads
+// than cores only adds contention, so the request is capped at the core count.
+#[must_use]
+pub fn solver_threads(requested: usize) -> usize {
+    requested.clamp(1, default_jobs())
+}
+
+// Applies `f` to every item using up to `jobs` worker threads. Results are handed to `emit` on the
+// calling thread as soon as they are available, but always in the same order as `items`, so output
+// stays deterministic regardless of which item finishes first.
+pub fn for_each_ordered<T, R, F, E>(items: &[T], jobs: usize, f: F, emit: E)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    E: FnMut(&T, R),
+{
+    for_each_ordered_init(items, jobs, || (), |(), item| f(item), emit);
+}
+
+// Like `for_each_ordered`, but every worker thread first creates some state with `init`, which is
+// then passed to `f` for each item that worker handles. Useful for per-thread caches that would
+// otherwise need a lock.
+pub fn for_each_ordered_init<T, S, R, I, F, E>(items: &[T], jobs: usize, init: I, f: F, mut emit: E)
+where
+    T: Sync,
+    R: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, &T) -> R + Sync,
+    E: FnMut(&T, R),
+{
+    let jobs = jobs.clamp(1, std::cmp::max(items.len(), 1));
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, init, f) = (&next, &init, &f);
+            scope.spawn(move || {
+                let mut state = init();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        return;
+                    };
+                    if sender.send((i, f(&mut state, item))).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        // Only the workers' clones should keep the channel open.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_emit = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_to_emit) {
+                emit(&items[next_to_emit], result);
+                next_to_emit += 1;
+            }
+        }
+    });
+}
+
+// Like `Iterator::map`, but `f` is applied using up to `jobs` worker threads. The results are in
+// the same order as `items`.
+pub fn map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    map_init(items, jobs, || (), |(), item| f(item))
+}
+
+// Like `map`, but with per-worker state as in `for_each_ordered_init`.
+pub fn map_init<T, S, R, I, F>(items: &[T], jobs: usize, init: I, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, &T) -> R + Sync,
+{
+    let mut results = Vec::with_capacity(items.len());
+    for_each_ordered_init(items, jobs, init, f, |_, result| results.push(result));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn collect(items: &[u64], jobs: usize) -> Vec<(u64, u64)> {
+        let mut results = vec![];
+        for_each_ordered(
+            items,
+            jobs,
+            |x| {
+                // Make earlier items finish later to shake out ordering bugs.
+                thread::sleep(Duration::from_millis(10 - x));
+                x * x
+            },
+            |x, y| results.push((*x, y)),
+        );
+        results
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Vec::<(u64, u64)>::new(), collect(&[], 4));
+    }
+
+    #[test]
+    fn single_job() {
+        assert_eq!(vec![(1, 1), (2, 4), (3, 9)], collect(&[1, 2, 3], 1));
+    }
+
+    #[test]
+    fn zero_jobs_runs_serially() {
+        assert_eq!(vec![(1, 1), (2, 4)], collect(&[1, 2], 0));
+    }
+
+    #[test]
+    fn results_stay_in_order() {
+        let items: Vec<_> = (0..10).collect();
+        let expected: Vec<_> = items.iter().map(|x| (*x, x * x)).collect();
+        assert_eq!(expected, collect(&items, 4));
+    }
+
+    #[test]
+    fn map_matches_sequential() {
+        let items: Vec<u64> = (0..100).collect();
+        let expected: Vec<_> = items.iter().map(|x| x * 3).collect();
+        assert_eq!(expected, map(&items, 8, |x| x * 3));
+    }
+
+    #[test]
+    fn map_init_keeps_state_per_worker() {
+        let items: Vec<u64> = (0..100).collect();
+        // With one worker, the state sees every item in order.
+        let expected: Vec<_> = (1..=100).collect();
+        assert_eq!(
+            expected,
+            map_init(
+                &items,
+                1,
+                || 0,
+                |seen, _| {
+                    *seen += 1;
+                    *seen
+                }
+            )
+        );
+        let counts = map_init(
+            &items,
+            4,
+            || 0,
+            |seen: &mut u64, _| {
+                *seen += 1;
+                *seen
+            },
+        );
+        assert!(counts.iter().all(|count| (1..=100).contains(count)));
+    }
+
+    #[test]
+    fn solver_threads_is_capped() {
+        assert_eq!(1, solver_threads(0));
+        assert_eq!(default_jobs(), solver_threads(usize::MAX));
+    }
+}
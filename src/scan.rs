@@ -0,0 +1,264 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::{oops, oops::Context, oops::Oops};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+// The text matched by one `{}` in a `scan()` pattern.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Field<'a> {
+    pub text: &'a str,
+    // 1-based, for error messages.
+    pub column: usize,
+    // The entire input, for error messages.
+    input: &'a str,
+}
+
+impl Field<'_> {
+    // Parses the field, pointing any error at the field's position in the input.
+    pub fn parse<T>(&self) -> Result<T, Oops>
+    where
+        T: FromStr,
+        T::Err: Into<Oops>,
+    {
+        self.text
+            .parse()
+            .map_err(|e: T::Err| e.into().with_text(self.input))
+            .at_column(self.column)
+    }
+}
+
+// Matches `input` against `pattern`, where each `{}` in the pattern matches everything up to the
+// next literal part of the pattern, or to the end of the input for a trailing `{}`. Fails, naming
+// the literal that was expected and where, unless all of `input` matches and the pattern has
+// exactly `N` fields.
+//
+// `scan!` is usually more convenient, since it also parses each field.
+pub fn scan<'a, const N: usize>(pattern: &str, input: &'a str) -> Result<[Field<'a>; N], Oops> {
+    let mismatch = |expected: &str, offset: usize| {
+        oops!("expected {expected}")
+            .at_column(offset + 1)
+            .with_text(input)
+    };
+
+    let mut literals = pattern.split("{}");
+    // `split` always yields at least one item.
+    let prefix = literals.next().unwrap_or_default();
+    let mut rest = input
+        .strip_prefix(prefix)
+        .ok_or_else(|| mismatch(&format!("{prefix:?}"), 0))?;
+
+    let mut fields = Vec::with_capacity(N);
+    for literal in literals {
+        let offset = input.len() - rest.len();
+        let len = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| mismatch(&format!("{literal:?}"), offset))?
+        };
+        fields.push(Field {
+            text: &rest[..len],
+            column: offset + 1,
+            input,
+        });
+        rest = &rest[len + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(mismatch("end of input", input.len() - rest.len()));
+    }
+
+    let len = fields.len();
+    fields
+        .try_into()
+        .map_err(|_| oops!("pattern {pattern:?} has {len} fields, not {N}"))
+}
+
+// Matches `input` against `pattern` as `scan()` does, then parses each field as the corresponding
+// type and returns them as a tuple. For example:
+//
+//     let (x, y) = scan!(line, "x={}, y={}", i64, i64)?;
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        $crate::scan::scan::<{ [$(stringify!($t)),+].len() }>($pattern, $input).and_then(
+            |fields| {
+                let mut fields = fields.iter();
+                Ok::<_, $crate::oops::Oops>(($(fields.next().unwrap().parse::<$t>()?,)+))
+            },
+        )
+    };
+}
+
+// Iterates over every integer in a string, in order, ignoring everything else, so prefer `scan!`
+// when the surrounding text matters. A `-` directly before the digits makes the integer negative,
+// unless it follows another digit: "3-5" is 3 then 5, so ranges stay positive.
+pub struct Integers<'a, T> {
+    input: &'a str,
+    pos: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Integers<'_, T>
+where
+    T: FromStr,
+    T::Err: Into<Oops>,
+{
+    // Collects the integers, failing unless there are exactly `N`.
+    pub fn exactly<const N: usize>(self) -> Result<[T; N], Oops> {
+        let input = self.input;
+        let values = self.collect::<Result<Vec<_>, _>>()?;
+        let len = values.len();
+        values
+            .try_into()
+            .map_err(|_| oops!("expected {N} integers, found {len}").with_text(input))
+    }
+}
+
+impl<T> Iterator for Integers<'_, T>
+where
+    T: FromStr,
+    T::Err: Into<Oops>,
+{
+    type Item = Result<T, Oops>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+        let digits = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let start = if digits > 0
+            && bytes[digits - 1] == b'-'
+            && (digits < 2 || !bytes[digits - 2].is_ascii_digit())
+        {
+            digits - 1
+        } else {
+            digits
+        };
+        let end = bytes[digits..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| digits + len);
+        self.pos = end;
+        Some(
+            Field {
+                text: &self.input[start..end],
+                column: start + 1,
+                input: self.input,
+            }
+            .parse(),
+        )
+    }
+}
+
+// Returns an iterator over every integer in `input`; see `Integers`.
+pub fn integers<T>(input: &str) -> Integers<'_, T> {
+    Integers {
+        input,
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+// Returns the `N` integers in `input`, failing if there are more or fewer.
+pub fn ints<const N: usize>(input: &str) -> Result<[i64; N], Oops> {
+    integers(input).exactly()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields() {
+        let [x, y] = scan("x={}, y={}", "x=12, y=-3").unwrap();
+        assert_eq!(("12", 3), (x.text, x.column));
+        assert_eq!(("-3", 9), (y.text, y.column));
+        let [a, b] = scan("{} and {}", "this and that").unwrap();
+        assert_eq!(("this", "that"), (a.text, b.text));
+        let [] = scan("no fields", "no fields").unwrap();
+    }
+
+    #[test]
+    fn typed() {
+        let (name, flow) = scan!(
+            "Valve AA has flow rate=0",
+            "Valve {} has flow rate={}",
+            String,
+            u32
+        )
+        .unwrap();
+        assert_eq!(("AA".to_string(), 0), (name, flow));
+        let (n,) = scan!("42", "{}", i64).unwrap();
+        assert_eq!(42, n);
+    }
+
+    #[test]
+    fn mismatched_literal() {
+        let e = scan::<2>("x={}, y={}", "x=1; y=2").unwrap_err();
+        assert_eq!(Some(3), e.location().column);
+        assert!(e.to_string().starts_with("oops: expected \", y=\""));
+
+        let e = scan::<1>("x={}", "y=1").unwrap_err();
+        assert_eq!(Some(1), e.location().column);
+        assert!(e.to_string().starts_with("oops: expected \"x=\""));
+    }
+
+    #[test]
+    fn trailing_input() {
+        let e = scan::<1>("{} units", "5 units of stuff").unwrap_err();
+        assert_eq!(Some(8), e.location().column);
+        assert!(e.to_string().starts_with("oops: expected end of input"));
+    }
+
+    #[test]
+    fn wrong_field_count() {
+        assert!(scan::<1>("{} {}", "a b").is_err());
+    }
+
+    #[test]
+    fn bad_field() {
+        let e = scan!("x=1, y=z", "x={}, y={}", i32, i32).unwrap_err();
+        assert_eq!(Some(8), e.location().column);
+        assert_eq!(Some("x=1, y=z"), e.location().text.as_deref());
+    }
+
+    #[test]
+    fn all_integers() {
+        let found: Vec<i64> = integers("x=-12, y=7: -3 and 4-5")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![-12, 7, -3, 4, 5], found);
+        assert_eq!(0, integers::<i64>("no numbers - here").count());
+    }
+
+    #[test]
+    fn fixed_count() {
+        assert_eq!(
+            [2, -18, -2, 15],
+            ints("Sensor at x=2, y=-18: beacon at x=-2, y=15").unwrap()
+        );
+        let e = ints::<2>("1 2 3").unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("oops: expected 2 integers, found 3"));
+        let [a, b] = integers::<u8>("from 1 to 255").exactly().unwrap();
+        assert_eq!((1, 255), (a, b));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let e = integers::<u8>("1 256").exactly::<2>().unwrap_err();
+        assert_eq!(Some(3), e.location().column);
+    }
+}
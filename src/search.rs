@@ -0,0 +1,474 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Everything a search learned: the cost to reach each visited node, how it was reached, and the
+// goal, if one was found. Searches stop as soon as a goal is reached, so nodes that are further
+// away than the goal may not have been visited; passing `|_| false` as the goal explores everything
+// reachable, e.g. for a flood fill.
+pub struct Search<N, C = usize> {
+    // The best known cost for each visited node and the node it was reached from. Start nodes have
+    // no parent.
+    visited: HashMap<N, (C, Option<N>)>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    #[must_use]
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    // The cost of the cheapest path to the goal.
+    #[must_use]
+    pub fn cost(&self) -> Option<C> {
+        self.cost_to(self.goal.as_ref()?)
+    }
+
+    // The cheapest path from a start node to the goal, including both ends.
+    #[must_use]
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    #[must_use]
+    pub fn cost_to(&self, node: &N) -> Option<C> {
+        self.visited.get(node).map(|(cost, _)| *cost)
+    }
+
+    #[must_use]
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut current = self.visited.get(node)?;
+        while let (_, Some(parent)) = current {
+            path.push(parent.clone());
+            current = &self.visited[parent];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[must_use]
+    pub fn contains(&self, node: &N) -> bool {
+        self.visited.contains_key(node)
+    }
+
+    // Iterates over every visited node and its cost, in no particular order.
+    pub fn visited(&self) -> impl Iterator<Item = (&N, C)> {
+        self.visited.iter().map(|(node, (cost, _))| (node, *cost))
+    }
+}
+
+// Breadth-first search, where every edge costs 1. All of `starts` are at distance 0.
+pub fn bfs<N, S, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: S,
+    mut is_goal: G,
+) -> Search<N>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut visited = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(v) = visited.entry(start.clone()) {
+            v.insert((0, None));
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, cost)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Search {
+                visited,
+                goal: Some(node),
+            };
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(v) = visited.entry(next.clone()) {
+                v.insert((cost + 1, Some(node.clone())));
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+    Search {
+        visited,
+        goal: None,
+    }
+}
+
+// Dijkstra's algorithm. `successors` yields each neighbor with the cost of the edge to it, which
+// must not be negative. All of `starts` are at cost `C::default()`, i.e. zero.
+pub fn dijkstra<N, C, S, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    successors: S,
+    is_goal: G,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+// A* search. `heuristic` estimates the remaining cost to the nearest goal; the result is only
+// guaranteed to be the cheapest path if it never overestimates.
+pub fn astar<N, C, S, I, H, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: S,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut visited: HashMap<N, (C, Option<N>)> = HashMap::new();
+    // Queue entries refer to nodes by index into `nodes`, so `N` itself needn't be `Ord`.
+    let mut nodes = vec![];
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(v) = visited.entry(start.clone()) {
+            v.insert((C::default(), None));
+            queue.push(Reverse((heuristic(&start), C::default(), nodes.len())));
+            nodes.push(start);
+        }
+    }
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        // A cheaper path to this node was found after this entry was queued.
+        if visited[&node].0 < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            return Search {
+                visited,
+                goal: Some(node),
+            };
+        }
+        for (next, edge_cost) in successors(&node) {
+            let next_cost = cost + edge_cost;
+            match visited.entry(next.clone()) {
+                Entry::Occupied(o) if o.get().0 <= next_cost => continue,
+                Entry::Occupied(mut o) => {
+                    o.insert((next_cost, Some(node.clone())));
+                }
+                Entry::Vacant(v) => {
+                    v.insert((next_cost, Some(node.clone())));
+                }
+            }
+            queue.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+    Search {
+        visited,
+        goal: None,
+    }
+}
+
+// Shortest distances between every pair of nodes in a graph whose nodes are numbered from 0.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistanceMatrix<C = usize> {
+    len: usize,
+    distances: Vec<Option<C>>,
+}
+
+impl<C: Copy> DistanceMatrix<C> {
+    // A matrix for `len` nodes where no node can reach any other.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        DistanceMatrix {
+            len,
+            distances: vec![None; len * len],
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The distance from `from` to `to`, or `None` if `to` is unreachable.
+    #[must_use]
+    pub fn get(&self, from: usize, to: usize) -> Option<C> {
+        self.distances[from * self.len + to]
+    }
+
+    pub fn set(&mut self, from: usize, to: usize, distance: Option<C>) {
+        self.distances[from * self.len + to] = distance;
+    }
+}
+
+// Floyd-Warshall, for graphs with `len` nodes given as a list of directed, weighted edges. Edge
+// costs must not be negative.
+pub fn floyd_warshall<C>(
+    len: usize,
+    edges: impl IntoIterator<Item = (usize, usize, C)>,
+) -> DistanceMatrix<C>
+where
+    C: Copy + Default + Ord + Add<Output = C>,
+{
+    let mut matrix = DistanceMatrix::new(len);
+    for i in 0..len {
+        matrix.set(i, i, Some(C::default()));
+    }
+    for (from, to, cost) in edges {
+        if matrix.get(from, to).is_none_or(|current| cost < current) {
+            matrix.set(from, to, Some(cost));
+        }
+    }
+    for k in 0..len {
+        for i in 0..len {
+            let Some(i_to_k) = matrix.get(i, k) else {
+                continue;
+            };
+            for j in 0..len {
+                let Some(k_to_j) = matrix.get(k, j) else {
+                    continue;
+                };
+                let through_k = i_to_k + k_to_j;
+                if matrix.get(i, j).is_none_or(|current| through_k < current) {
+                    matrix.set(i, j, Some(through_k));
+                }
+            }
+        }
+    }
+    matrix
+}
+
+// Shortest distances between each pair of `nodes`, which must be distinct, using a BFS from each.
+// Paths may pass through nodes that are not in `nodes`; entry (i, j) is the distance from
+// `nodes[i]` to `nodes[j]`. This is generally cheaper than Floyd-Warshall when only a few nodes of
+// a large, unweighted graph are of interest.
+pub fn all_pairs_bfs<N, S, I>(nodes: &[N], mut successors: S) -> DistanceMatrix
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut matrix = DistanceMatrix::new(nodes.len());
+    for (i, from) in nodes.iter().enumerate() {
+        // Stop early once every node of interest has been reached.
+        let mut remaining = nodes.len();
+        let search = bfs([from.clone()], &mut successors, |n| {
+            if nodes.contains(n) {
+                remaining -= 1;
+            }
+            remaining == 0
+        });
+        for (j, to) in nodes.iter().enumerate() {
+            matrix.set(i, j, search.cost_to(to));
+        }
+    }
+    matrix
+}
+
+// A graph reduced to just the nodes of interest, with the distances between them. Each node is
+// assigned a compact id, in the order the nodes were given to `contract()`.
+pub struct Contracted<N> {
+    nodes: Vec<N>,
+    ids: HashMap<N, usize>,
+    distances: DistanceMatrix,
+}
+
+impl<N: Eq + Hash> Contracted<N> {
+    #[must_use]
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    #[must_use]
+    pub fn id(&self, node: &N) -> Option<usize> {
+        self.ids.get(node).copied()
+    }
+
+    #[must_use]
+    pub fn distance(&self, from: usize, to: usize) -> Option<usize> {
+        self.distances.get(from, to)
+    }
+
+    #[must_use]
+    pub fn distances(&self) -> &DistanceMatrix {
+        &self.distances
+    }
+
+    #[must_use]
+    pub fn into_distances(self) -> DistanceMatrix {
+        self.distances
+    }
+}
+
+// Contracts an unweighted graph to the `interesting` nodes. Duplicate nodes are ignored.
+pub fn contract<N, S, I>(interesting: impl IntoIterator<Item = N>, successors: S) -> Contracted<N>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut nodes = vec![];
+    let mut ids = HashMap::new();
+    for node in interesting {
+        if let Entry::Vacant(v) = ids.entry(node.clone()) {
+            v.insert(nodes.len());
+            nodes.push(node);
+        }
+    }
+    let distances = all_pairs_bfs(&nodes, successors);
+    Contracted {
+        nodes,
+        ids,
+        distances,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small weighted graph where the direct edge 0 -> 3 is more expensive than going around.
+    fn edges(n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_path() {
+        let search = bfs([0], |n| edges(n).into_iter().map(|(n, _)| n), |n| *n == 3);
+        assert_eq!(Some(1), search.cost());
+        assert_eq!(Some(vec![0, 3]), search.path());
+        assert_eq!(Some(&3), search.goal());
+    }
+
+    #[test]
+    fn bfs_multiple_starts() {
+        // Counting down to zero, starting from whichever start is closest.
+        let search = bfs([10, 4, 7], |n: &i32| [n - 1], |n| *n == 0);
+        assert_eq!(Some(4), search.cost());
+        assert_eq!(Some(vec![4, 3, 2, 1, 0]), search.path());
+        assert_eq!(Some(0), search.cost_to(&7));
+    }
+
+    #[test]
+    fn bfs_flood_fill() {
+        let search = bfs(
+            [0],
+            |n: &i32| [n - 1, n + 1].into_iter().filter(|n| n.abs() <= 3),
+            |_| false,
+        );
+        assert_eq!(None, search.goal());
+        assert_eq!(7, search.visited().count());
+        assert!(search.contains(&-3));
+        assert!(!search.contains(&4));
+        assert_eq!(Some(3), search.cost_to(&3));
+    }
+
+    #[test]
+    fn dijkstra_path() {
+        let search = dijkstra([0], edges, |n| *n == 3);
+        assert_eq!(Some(6), search.cost());
+        assert_eq!(Some(vec![0, 1, 2, 3]), search.path());
+        assert_eq!(None, dijkstra([1], edges, |n| *n == 0).cost());
+    }
+
+    #[test]
+    fn floyd_warshall_matches_dijkstra() {
+        let edge_list: Vec<_> = (0..4)
+            .flat_map(|from| {
+                edges(&from)
+                    .into_iter()
+                    .map(move |(to, cost)| (from as usize, to as usize, cost))
+            })
+            .collect();
+        let matrix = floyd_warshall(4, edge_list);
+        for from in 0..4 {
+            for to in 0..4 {
+                let expected = dijkstra([from], edges, |n| *n == to).cost();
+                assert_eq!(expected, matrix.get(from as usize, to as usize));
+            }
+        }
+        assert_eq!(Some(6), matrix.get(0, 3));
+        assert_eq!(None, matrix.get(3, 0));
+    }
+
+    #[test]
+    fn contraction() {
+        // A line 0 - 1 - ... - 9, contracted to just a few nodes.
+        let successors = |n: &i32| [n - 1, n + 1].into_iter().filter(|n| (0..10).contains(n));
+        let contracted = contract([7, 2, 9, 2], successors);
+        assert_eq!(&[7, 2, 9], contracted.nodes());
+        assert_eq!(Some(1), contracted.id(&2));
+        assert_eq!(None, contracted.id(&3));
+        assert_eq!(Some(5), contracted.distance(0, 1));
+        assert_eq!(Some(7), contracted.distance(2, 1));
+        assert_eq!(Some(0), contracted.distance(2, 2));
+        assert_eq!(
+            &all_pairs_bfs(&[7, 2, 9], successors),
+            contracted.distances()
+        );
+        assert_eq!(3, contracted.into_distances().len());
+    }
+
+    #[test]
+    fn astar_grid() {
+        use crate::geometry::{Bounds2, Point2};
+
+        // Walking around a wall at x = 2 that is open only at y = 4.
+        let bounds = Bounds2::new(Point2::new(0, 0), Point2::new(4, 4));
+        let open = |p: &Point2| p.x != 2 || p.y == 4;
+        let goal = Point2::new(4, 0);
+        let search = astar(
+            [Point2::new(0, 0)],
+            |p| {
+                p.bounded_neighbors(&bounds)
+                    .filter(open)
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| (goal - *p).manhattan(),
+            |p| *p == goal,
+        );
+        assert_eq!(Some(12), search.cost());
+        let path = search.path().unwrap();
+        assert_eq!(13, path.len());
+        assert!(path.iter().all(open));
+    }
+}
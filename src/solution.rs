@@ -0,0 +1,190 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::input::InputSource;
+use crate::parallel;
+use crate::{oops, oops::Oops};
+use std::env;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+// Every day is split into the same three phases: `parse` turns the raw puzzle input into some
+// day-specific representation, which is then shared by `part1` and `part2`.
+pub trait Solution {
+    type Puzzle;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Oops>;
+    fn part1(puzzle: &Self::Puzzle) -> Result<Self::Part1, Oops>;
+    fn part2(puzzle: &Self::Puzzle) -> Result<Self::Part2, Oops>;
+
+    // Solutions that can split their work across threads override these; `threads` is always at
+    // least 1. The default ignores it and runs the sequential part.
+    fn part1_threaded(puzzle: &Self::Puzzle, threads: usize) -> Result<Self::Part1, Oops> {
+        let _ = threads;
+        Self::part1(puzzle)
+    }
+
+    fn part2_threaded(puzzle: &Self::Puzzle, threads: usize) -> Result<Self::Part2, Oops> {
+        let _ = threads;
+        Self::part2(puzzle)
+    }
+}
+
+#[derive(Debug)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+// Wall time spent in each phase of a single run.
+#[derive(Clone, Copy, Debug)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub answers: Answers,
+    // One entry per repetition.
+    pub timings: Vec<Timings>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    #[must_use]
+    pub fn from_samples<I: IntoIterator<Item = Duration>>(samples: I) -> Option<Self> {
+        let mut samples: Vec<_> = samples.into_iter().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len() % 2 == 0 {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Some(Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+// Type-erased entry point for a `Solution`, so that days with different puzzle and answer types can
+// live in the same table.
+pub type Solver = fn(&str, usize, usize) -> Result<Run, Oops>;
+
+// Runs every phase of `S` `runs` times (at least once), timing each phase separately. The puzzle
+// is re-parsed for every repetition so that `parse` is measured as often as the parts. The parts
+// may use up to `threads` threads, capped by `parallel::solver_threads`.
+pub fn measure<S: Solution>(input: &str, runs: usize, threads: usize) -> Result<Run, Oops> {
+    let threads = parallel::solver_threads(threads);
+    let mut timings = Vec::with_capacity(runs);
+    loop {
+        let start = Instant::now();
+        let puzzle = S::parse(input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part1 = S::part1_threaded(&puzzle, threads)?;
+        let part1_time = start.elapsed();
+
+        let start = Instant::now();
+        let part2 = S::part2_threaded(&puzzle, threads)?;
+        let part2_time = start.elapsed();
+
+        timings.push(Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        });
+        if timings.len() >= runs {
+            return Ok(Run {
+                answers: Answers {
+                    part1: part1.to_string(),
+                    part2: part2.to_string(),
+                },
+                timings,
+            });
+        }
+    }
+}
+
+// Shared `main` for the per-day binaries. Takes an optional argument naming the input file, or `-`
+// to read from stdin; otherwise, the day's file in the inputs directory is used.
+pub fn run<S: Solution>(day: u32) -> Result<(), Oops> {
+    let mut args = env::args().skip(1);
+    let arg = args.next();
+    if args.next().is_some() {
+        return Err(oops!("usage: day{day:02} [<input>|-]"));
+    }
+    let input = InputSource::from_arg(day, arg.as_deref()).load()?;
+
+    let answers = measure::<S>(&input, 1, 1)?.answers;
+
+    println!("{}", answers.part1);
+    println!("{}", answers.part2);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn stats_empty() {
+        assert_eq!(None, Stats::from_samples(vec![]));
+    }
+
+    #[test]
+    fn stats_odd() {
+        assert_eq!(
+            Some(Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(7)
+            }),
+            Stats::from_samples(vec![ms(7), ms(1), ms(3)])
+        );
+    }
+
+    #[test]
+    fn stats_even() {
+        assert_eq!(
+            Some(Stats {
+                min: ms(1),
+                median: ms(4),
+                max: ms(9)
+            }),
+            Stats::from_samples(vec![ms(9), ms(5), ms(1), ms(3)])
+        );
+    }
+}